@@ -2,7 +2,7 @@ use std::io;
 use std::fs::{self};
 use std::path::{Path, PathBuf};
 
-pub fn get_rs_file_paths<'a, P: AsRef<Path>>(dir: P) -> Vec<PathBuf> {
+pub fn get_rs_file_paths<P: AsRef<Path>>(dir: P) -> Vec<PathBuf> {
     let mut results = vec![];
     match visit_dirs(dir.as_ref(), &mut results) {
         Ok(_) => (),
@@ -1,5 +1,44 @@
 pub mod to_dot;
 
+/// How traits listed in `#[derive(...)]` are shown.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum DeriveStyle {
+    /// A `<<derive: Debug, Clone>>` line in the class header.
+    #[default]
+    Stereotype,
+    /// Realization edges to the derived traits, with stub nodes for the ones not in the graph.
+    Realization,
+}
+
+/// Options on how the UML graph is exported.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Hide fields, methods and items that are not `pub`.
+    pub public_only: bool,
+    /// How derived traits are shown.
+    pub derive_style: DeriveStyle,
+    /// Add doc comments as notes next to the documented items, besides their tooltips.
+    pub doc_notes: bool,
+    /// Colour `unsafe` functions, and classes with `unsafe` methods, in red.
+    pub highlight_unsafe: bool,
+    /// Show `Self` in method signatures as the name of the type or trait it stands for.
+    pub resolve_self: bool,
+    /// List only the names of the traits a class implements, instead of a compartment of methods per trait.
+    pub collapse_trait_impls: bool,
+    /// Draw stub nodes, labelled with their full paths, for the traits and types of other crates that relations point at.
+    pub external_stubs: bool,
+    /// Crates whose items get stub nodes, any crate's when empty.
+    pub stub_crates_allowed: Vec<String>,
+    /// Crates whose items never get stub nodes, like `std`.
+    pub stub_crates_denied: Vec<String>,
+    /// Draw dependencies on well-known std macros like `println!` and `vec!`, which are left out by default.
+    pub std_macros: bool,
+}
+
 pub trait GraphExporter {
-    fn to_string(&self) -> String;
-}
\ No newline at end of file
+    fn to_string(&self) -> String {
+        self.to_string_with_options(&ExportOptions::default())
+    }
+
+    fn to_string_with_options(&self, options: &ExportOptions) -> String;
+}
@@ -1,49 +1,139 @@
 mod uml_fn;
 mod uml_class;
+mod uml_macro;
+mod uml_static;
 mod uml_relation;
 mod uml_graph;
+mod dot_statement;
 
-use dot_graph::{Edge, Node, Graph, Subgraph};
+use dot_graph::{Style, Arrow};
+use dot_statement::{Edge, Node};
 use crate::uml_entity::*;
 
-use super::GraphExporter;
+use super::{GraphExporter, ExportOptions, DeriveStyle};
 enum DotEntity {
     Edge(Edge),
-    Node(Node)
+    Node(Node),
+    // items outside the crate and the edges to them, declared once at the top whichever modules use them
+    // an edge inside of a cluster would pull its ends into the cluster
+    SharedEdge(Edge),
+    SharedNode(Node)
+}
+
+impl DotEntity {
+    fn into_shared(self) -> DotEntity {
+        match self {
+            DotEntity::Edge(edge) => DotEntity::SharedEdge(edge),
+            DotEntity::Node(node) => DotEntity::SharedNode(node),
+            shared => shared
+        }
+    }
 }
 
 trait HasDotEntity {
-    fn get_dot_entities(&self, name_prefix: &str) -> Vec<DotEntity>;
+    fn get_dot_entities(&self, name_prefix: &str, options: &ExportOptions) -> Vec<DotEntity>;
 }
 
 impl GraphExporter for UMLGraph {
-    fn to_string(&self) -> String {
-        let (node_list, edge_list) = get_node_and_edge_list(self.get_dot_entities(""));
-
-        // generate digraph from nodes and edges
-        let mut graph = Graph::new("ast", dot_graph::Kind::Digraph);
-        for node in node_list {
-            graph.add_node(node)
+    fn to_string_with_options(&self, options: &ExportOptions) -> String {
+        // modules go first, then the graph's own nodes, the shared ones and the edges
+        let mut lines: Vec<String> = vec![];
+        // the crate doc shows up when hovering the background, as the modules' docs do on their clusters
+        if let Some(doc) = &self.doc {
+            lines.push(format!("tooltip=\"{}\";", escape_dot_string(doc)));
         }
-        for edge in edge_list {
-            graph.add_edge(edge)
+        let mut shared_nodes: Vec<Node> = vec![];
+        let mut shared_edges: Vec<Edge> = vec![];
+        for m in self.modules.values() {
+            lines.append(&mut get_module_lines(m, "", options, &mut shared_nodes, &mut shared_edges));
         }
+        let (node_list, edge_list) = get_node_and_edge_list(self.get_dot_entities("", options), &mut shared_nodes, &mut shared_edges);
+        let mut shared_names: Vec<String> = node_list.iter().map(|n| n.name.clone()).collect();
+        shared_nodes.retain(|n| {
+            let is_new = !shared_names.contains(&n.name);
+            shared_names.push(n.name.clone());
+            is_new
+        });
+        lines.extend(node_list.iter().chain(shared_nodes.iter()).map(|n| n.to_dot_string()));
+        lines.extend(edge_list.iter().chain(shared_edges.iter()).map(|e| e.to_dot_string()));
+        let body: String = lines.iter().map(|l| format!("    {}\n", l)).collect();
+        ["digraph ast {\n", &body, "}\n"].concat()
+    }
+}
 
-        // generate digraph from modules
-        for (name, m) in &self.modules {
-            let name_prefix = format!("{}.", &m.name);
-            let (node_list, edge_list) = get_node_and_edge_list(m.get_dot_entities(&name_prefix));
-            let mut subgraph = Subgraph::new(&format!("cluster_{}", name)).label(name);
-            subgraph.add_nodes(node_list);
-            edge_list.iter().for_each(|e| subgraph.add_edge(e.clone()));
-            graph.add_subgraph(subgraph);
-        }
+fn get_module_lines(module: &UMLGraph, parent_prefix: &str, options: &ExportOptions, shared_nodes: &mut Vec<Node>, shared_edges: &mut Vec<Edge>) -> Vec<String> {
+    // module as a cluster subgraph, with nested modules as clusters inside of it after its own nodes and edges
+    let name_prefix = format!("{}{}.", parent_prefix, &module.name);
+    let cluster_name = format!("cluster_{}", name_prefix.trim_end_matches('.').replace('.', "_"));
+    let label = match &module.stereotype {
+        Some(st) => format!("<<{}>>\\n{}", st, module.name),
+        None => module.name.clone()
+    };
+    let mut inner_lines = vec![format!("label=\"{}\";", label)];
+    if let Some(doc) = &module.doc {
+        inner_lines.push(format!("tooltip=\"{}\";", escape_dot_string(doc)));
+    }
+    let (node_list, edge_list) = get_node_and_edge_list(module.get_dot_entities(&name_prefix, options), shared_nodes, shared_edges);
+    inner_lines.extend(node_list.iter().map(|n| n.to_dot_string()));
+    inner_lines.extend(edge_list.iter().map(|e| e.to_dot_string()));
+    for m in module.modules.values() {
+        inner_lines.append(&mut get_module_lines(m, &name_prefix, options, shared_nodes, shared_edges));
+    }
 
-        return graph.to_dot_string().unwrap();
+    let mut lines = vec![format!("subgraph {} {{", cluster_name)];
+    lines.extend(inner_lines.into_iter().map(|l| format!("    {}", l)));
+    lines.push(String::from("}"));
+    lines
+}
+
+const UNSAFE_COLOR: &str = "red";
+// other crates' items are greyed out next to the crate's own ones
+const STUB_COLOR: &str = "gray";
+
+fn get_qualifier_text(qualifiers: &[UMLQualifier]) -> String {
+    // qualifiers as stereotypes in front of the fn, e.g. `«async» «unsafe» `
+    qualifiers
+        .iter()
+        .map(|q| ["«", &q.get_stereotype_name(), "» "].concat())
+        .collect()
+}
+
+fn get_doc_note_entities(node_name: &str, doc: &str) -> Vec<DotEntity> {
+    // note with left-justified lines, tied to the documented node with a dashed line
+    // the double underscore keeps it apart from items, which the naming lints keep from using one
+    let note_name = [node_name, "__doc"].concat();
+    let note_label: String = doc
+        .lines()
+        .map(|l| [&escape_dot_string(l), r"\l"].concat())
+        .collect();
+    vec![
+        DotEntity::Node(Node::new(&note_name).label(&note_label).shape(Some("note"))),
+        DotEntity::Edge(Edge::new(&note_name, node_name, "").style(Style::Dashed).end_arrow(Arrow::none())),
+    ]
+}
+
+fn escape_record_text(text: &str) -> String {
+    // record payloads of variants contain characters that have special meaning in record labels
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if matches!(c, '{' | '}' | '|' | '<' | '>' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
     }
+    escaped
+}
+
+fn escape_html_text(text: &str) -> String {
+    // text of HTML-like labels, where the markup's characters are entities
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
 }
 
-fn get_node_and_edge_list(dot_entities: Vec<DotEntity>) -> (Vec<Node>, Vec<Edge>) {
+fn escape_dot_string(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn get_node_and_edge_list(dot_entities: Vec<DotEntity>, shared_nodes: &mut Vec<Node>, shared_edges: &mut Vec<Edge>) -> (Vec<Node>, Vec<Edge>) {
     // transform DotEntity to nodes and edges that 'dot' can use
     // let mut label_list: Vec<&str> = vec![];
     let mut edge_list: Vec<Edge> = vec![];
@@ -56,6 +146,12 @@ fn get_node_and_edge_list(dot_entities: Vec<DotEntity>) -> (Vec<Node>, Vec<Edge>
             DotEntity::Node(node) => {
                 node_list.push(node);
             },
+            DotEntity::SharedEdge(edge) => {
+                shared_edges.push(edge);
+            },
+            DotEntity::SharedNode(node) => {
+                shared_nodes.push(node);
+            },
         }
     }
     (node_list, edge_list)
@@ -74,14 +170,207 @@ mod tests {
 
         let dot_string = uml_graph.to_string();
         let target_string = r#"digraph ast {
-    "Main"[label="{Main|a: String\lb: String|main() -> ()\lmain1()}"][shape="record"];
-    "MainTrait"[label="{Interface\lMainTrait|main() -> ()}"][shape="record"];
+    "Main"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Main</TD></TR><HR/><TR><TD ALIGN="LEFT">-a: String<BR ALIGN="LEFT"/>-b: String<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-main() -&gt; ()</U><BR ALIGN="LEFT"/><U>-main1()</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "MainTrait"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>MainTrait</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>+main() -&gt; ()</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
     "test"[label="test"];
 }
 "#;
         assert_eq!(dot_string, target_string);
     }
 
+    #[test]
+    fn test_uml_enum_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        uml_graph.add_struct(UMLClass::new_enum("Shape", vec![String::from("Circle(f64)"), String::from("Rect { w: f64, h: f64 }")], vec![String::from("area(&self) -> f64")]));
+        uml_graph.add_struct(UMLClass::new_enum("Empty", vec![], vec![]));
+
+        let dot_string = uml_graph.to_string();
+        let target_string = r#"digraph ast {
+    "Shape"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>&lt;&lt;enumeration&gt;&gt;<BR ALIGN="LEFT"/>Shape</TD></TR><HR/><TR><TD ALIGN="LEFT">Circle(f64)<BR ALIGN="LEFT"/>Rect { w: f64, h: f64 }<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">-area(&amp;self) -&gt; f64<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Empty"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>&lt;&lt;enumeration&gt;&gt;<BR ALIGN="LEFT"/>Empty</TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_uml_trait_items_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        let mut walker = UMLClass::new("Walker", vec![String::from("type Item")], vec![String::from("next(&mut self) -> Option<Self::Item>")], UMLClassKind::UMLTrait);
+        let mut count = UMLFn::from_full_name("count(&mut self) -> usize");
+        count.has_default = true;
+        count.visibility = UMLVisibility::Public;
+        walker.add_method(count);
+        uml_graph.add_struct(walker);
+
+        let dot_string = uml_graph.to_string();
+        let target_string = r#"digraph ast {
+    "Walker"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Walker</TD></TR><HR/><TR><TD ALIGN="LEFT">+type Item<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">+next(&amp;mut self) -&gt; Option&lt;Self::Item&gt;<BR ALIGN="LEFT"/>«default» +count(&amp;mut self) -&gt; usize<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_resolve_self_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        uml_graph.add_struct(UMLClass::new("Walker", vec![], vec![String::from("merge(&self, other: &Self) -> SelfRef")], UMLClassKind::UMLClass));
+        let options = ExportOptions { resolve_self: true, ..Default::default() };
+
+        let dot_string = uml_graph.to_string_with_options(&options);
+        let target_string = r#"digraph ast {
+    "Walker"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Walker</TD></TR><HR/><TR><TD ALIGN="LEFT">-merge(&amp;self, other: &amp;Walker) -&gt; SelfRef<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_trait_impl_methods_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        let mut foo = UMLClass::new("Foo", vec![], vec![String::from("new() -> Self")], UMLClassKind::UMLClass);
+        foo.add_trait_methods("Display", &mut vec![UMLFn::from_full_name("fmt(&self, f)")]);
+        foo.add_trait_methods("Iterator", &mut vec![UMLFn::from_full_name("next(&mut self)"), UMLFn::from_full_name("count(self)")]);
+        uml_graph.add_struct(foo);
+
+        let dot_string = uml_graph.to_string();
+        let target_string = r#"digraph ast {
+    "Foo"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Foo</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-new() -&gt; Self</U><BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">Display: fmt(&amp;self, f)<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">Iterator: next(&amp;mut self)<BR ALIGN="LEFT"/>Iterator: count(self)<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+
+        let dot_string = uml_graph.to_string_with_options(&ExportOptions { collapse_trait_impls: true, ..Default::default() });
+        let target_string = r#"digraph ast {
+    "Foo"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Foo</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-new() -&gt; Self</U><BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">Display<BR ALIGN="LEFT"/>Iterator<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_external_stubs_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        uml_graph.add_struct(UMLClass::new("Walker", vec![], vec![], UMLClassKind::UMLClass));
+        uml_graph.add_relation(UMLRelation::new("Walker", "Iterator", UMLRelationKind::UMLRealization));
+        uml_graph.add_relation(UMLRelation::new("Walker", "reqwest.Client", UMLRelationKind::UMLComposition));
+        uml_graph.add_relation(UMLRelation::new("Walker", "Unknown", UMLRelationKind::UMLComposition));
+        let options = ExportOptions { external_stubs: true, stub_crates_allowed: vec![String::from("reqwest")], ..Default::default() };
+
+        let dot_string = uml_graph.to_string_with_options(&options);
+        let target_string = r#"digraph ast {
+    "Walker"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Walker</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "reqwest.Client"[label="reqwest::Client"][style="dashed"][color="gray"][shape="record"];
+    "Walker" -> "reqwest.Client"[label=""][arrowhead="diamond"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_shared_derive_stubs_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        for module_name in ["a", "b"] {
+            let mut module = UMLGraph::new(module_name);
+            let mut item = UMLClass::new("Item", vec![], vec![], UMLClassKind::UMLClass);
+            item.set_derive_names(vec![String::from("serde::Serialize"), String::from("Debug")]);
+            module.add_struct(item);
+            uml_graph.add_module(module);
+        }
+        let options = ExportOptions { derive_style: DeriveStyle::Realization, ..Default::default() };
+
+        // a full path is declared once outside of the modules, bare names stay in each of them
+        let dot_string = uml_graph.to_string_with_options(&options);
+        let target_string = r#"digraph ast {
+    subgraph cluster_a {
+        label="a";
+        "a.Item"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Item</TD></TR></TABLE>>][margin="0"][shape="box"];
+        "a.Debug"[label="Interface\lDebug"][style="dashed"][shape="record"];
+        "a.Item" -> "a.Debug"[label=""][style="dashed"][arrowhead="onormal"];
+    }
+    subgraph cluster_b {
+        label="b";
+        "b.Item"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Item</TD></TR></TABLE>>][margin="0"][shape="box"];
+        "b.Debug"[label="Interface\lDebug"][style="dashed"][shape="record"];
+        "b.Item" -> "b.Debug"[label=""][style="dashed"][arrowhead="onormal"];
+    }
+    "serde.Serialize"[label="Interface\lserde::Serialize"][style="dashed"][shape="record"];
+    "a.Item" -> "serde.Serialize"[label=""][style="dashed"][arrowhead="onormal"];
+    "b.Item" -> "serde.Serialize"[label=""][style="dashed"][arrowhead="onormal"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_shared_external_stubs_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        for module_name in ["a", "b"] {
+            let mut module = UMLGraph::new(module_name);
+            module.add_struct(UMLClass::new("Item", vec![], vec![], UMLClassKind::UMLClass));
+            module.add_relation(UMLRelation::new("Item", "serde.Serialize", UMLRelationKind::UMLRealization));
+            uml_graph.add_module(module);
+        }
+        let options = ExportOptions { external_stubs: true, ..Default::default() };
+
+        // one stub for all modules, with the edges to it outside of them too
+        let dot_string = uml_graph.to_string_with_options(&options);
+        let target_string = r#"digraph ast {
+    subgraph cluster_a {
+        label="a";
+        "a.Item"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Item</TD></TR></TABLE>>][margin="0"][shape="box"];
+    }
+    subgraph cluster_b {
+        label="b";
+        "b.Item"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Item</TD></TR></TABLE>>][margin="0"][shape="box"];
+    }
+    "serde.Serialize"[label="Interface\lserde::Serialize"][style="dashed"][color="gray"][shape="record"];
+    "a.Item" -> "serde.Serialize"[label=""][style="dashed"][arrowhead="onormal"];
+    "b.Item" -> "serde.Serialize"[label=""][style="dashed"][arrowhead="onormal"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_public_only_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        let mut main = UMLClass::new("Main", vec![String::from("a: String")], vec![String::from("main()")], UMLClassKind::UMLClass);
+        main.visibility = UMLVisibility::Public;
+        let mut b = UMLField::from_full_name("b: String");
+        b.visibility = UMLVisibility::Public;
+        main.add_field(b);
+        let mut new = UMLFn::new("new", "new() -> Self");
+        new.visibility = UMLVisibility::Public;
+        main.add_method(new);
+        uml_graph.add_struct(main);
+        uml_graph.add_struct(UMLClass::new("Hidden", vec![], vec![], UMLClassKind::UMLClass));
+        uml_graph.add_fn(UMLFn::new("test", "test()"));
+        uml_graph.add_relation(UMLRelation::new("Main", "Hidden", UMLRelationKind::UMLComposition));
+
+        let dot_string = uml_graph.to_string_with_options(&ExportOptions { public_only: true, ..Default::default() });
+        let target_string = r#"digraph ast {
+    "Main"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Main</TD></TR><HR/><TR><TD ALIGN="LEFT">+b: String<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT"><U>+new() -&gt; Self</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_uml_class_generics_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        let mut buffer = UMLClass::new("Buffer", vec![String::from("data: Vec<T>")], vec![], UMLClassKind::UMLClass);
+        buffer.set_generic_names(vec![String::from("'a"), String::from("T: Iterator<Item = u8>")]);
+        uml_graph.add_struct(buffer);
+
+        let dot_string = uml_graph.to_string();
+        let target_string = r#"digraph ast {
+    "Buffer"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>'a, T: Iterator&lt;Item = u8&gt;<BR ALIGN="RIGHT"/>Buffer</TD></TR><HR/><TR><TD ALIGN="LEFT">-data: Vec&lt;T&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
     #[test]
     fn test_uml_fn_relations() {
         let mut uml_graph = UMLGraph::new("");
@@ -111,7 +400,7 @@ mod tests {
         let dot_string = uml_graph.to_string();
         let target_string = 
 r#"digraph ast {
-    "Mock"[label="{Mock|mock_fn()}"][shape="record"];
+    "Mock"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Mock</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-mock_fn()</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
     "f1"[label="f1"];
     "f2"[label="f2"];
     "f1" -> "Mock"[label=""][style="dashed"][arrowhead="vee"];
@@ -131,8 +420,8 @@ r#"digraph ast {
         let dot_string = uml_graph.to_string();
         let target_string = 
 r#"digraph ast {
-    "Mock"[label="{Mock|b: *mut B}"][shape="record"];
-    "B"[label="B"][shape="record"];
+    "Mock"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Mock</TD></TR><HR/><TR><TD ALIGN="LEFT">-b: *mut B<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>B</TD></TR></TABLE>>][margin="0"][shape="box"];
     "Mock" -> "B"[label=""][arrowtail="odiamond"];
 }
 "#;
@@ -149,8 +438,8 @@ r#"digraph ast {
         let dot_string = uml_graph.to_string();
         let target_string = 
 r#"digraph ast {
-    "Mock"[label="{Mock|c: C}"][shape="record"];
-    "C"[label="C"][shape="record"];
+    "Mock"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Mock</TD></TR><HR/><TR><TD ALIGN="LEFT">-c: C<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "C"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>C</TD></TR></TABLE>>][margin="0"][shape="box"];
     "Mock" -> "C"[label=""][arrowhead="diamond"];
 }
 "#;
@@ -167,14 +456,32 @@ r#"digraph ast {
         let dot_string = uml_graph.to_string();
         let target_string = 
 r#"digraph ast {
-    "Mock"[label="Mock"][shape="record"];
-    "D"[label="{Interface\lD|a(&self) -> Option<T>}"][shape="record"];
+    "Mock"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Mock</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "D"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>D</TD></TR><HR/><TR><TD ALIGN="LEFT">+a(&amp;self) -&gt; Option&lt;T&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
     "Mock" -> "D"[label=""][style="dashed"][arrowhead="onormal"];
 }
 "#;
         assert_eq!(dot_string, target_string);
     }
 
+    #[test]
+    fn test_uml_trait_generalization() {
+        let mut uml_graph = UMLGraph::new("");
+        uml_graph.add_struct(UMLClass::new("Reader", vec![], vec![], UMLClassKind::UMLTrait));
+        uml_graph.add_struct(UMLClass::new("Source", vec![], vec![], UMLClassKind::UMLTrait));
+        uml_graph.add_relation(UMLRelation::new("Reader", "Source", UMLRelationKind::UMLGeneralization));
+
+        let dot_string = uml_graph.to_string();
+        let target_string = 
+r#"digraph ast {
+    "Reader"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Reader</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Source"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Source</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Reader" -> "Source"[label=""][arrowhead="onormal"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
     #[test]
     fn test_uml_class_association() {
         let mut uml_graph = UMLGraph::new("");
@@ -187,9 +494,9 @@ r#"digraph ast {
         let dot_string = uml_graph.to_string();
         let target_string = 
 r#"digraph ast {
-    "Mock"[label="{Mock|e2() -> E2}"][shape="record"];
-    "E1"[label="{E1|b() -> Mock}"][shape="record"];
-    "E2"[label="{E2|a() -> Mock}"][shape="record"];
+    "Mock"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Mock</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-e2() -&gt; E2</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "E1"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>E1</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-b() -&gt; Mock</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "E2"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>E2</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-a() -&gt; Mock</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
     "E1" -> "Mock"[label=""][arrowhead="vee"];
     "E2" -> "Mock"[label=""][arrowhead="none"];
 }
@@ -213,9 +520,9 @@ r#"digraph ast {
 r#"digraph ast {
     subgraph cluster_mock_mod {
         label="mock_mod";
-        "mock_mod.Mock"[label="{Mock|e2() -> E2}"][shape="record"];
-        "mock_mod.E1"[label="{E1|b() -> Mock}"][shape="record"];
-        "mock_mod.E2"[label="{E2|a() -> Mock}"][shape="record"];
+        "mock_mod.Mock"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Mock</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-e2() -&gt; E2</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+        "mock_mod.E1"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>E1</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-b() -&gt; Mock</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+        "mock_mod.E2"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>E2</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-a() -&gt; Mock</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
         "mock_mod.E1" -> "mock_mod.Mock"[label=""][arrowhead="vee"];
         "mock_mod.E2" -> "mock_mod.Mock"[label=""][arrowhead="none"];
     }
@@ -245,7 +552,7 @@ r#"digraph ast {
 r#"digraph ast {
     subgraph cluster_hello_mod {
         label="hello_mod";
-        "hello_mod.Hello"[label="Hello"][shape="record"];
+        "hello_mod.Hello"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Hello</TD></TR></TABLE>>][margin="0"][shape="box"];
         "hello_mod.hello"[label="hello"];
     }
     subgraph cluster_mock_mod {
@@ -255,6 +562,64 @@ r#"digraph ast {
         "mock_mod.mock" -> "hello_mod.hello"[label=""][style="dashed"][arrowhead="vee"];
     }
 }
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_nested_mods() {
+        let mut uml_graph = UMLGraph::new("");
+        let mut outer_mod = UMLGraph::new("outer");
+        let mut inner_mod = UMLGraph::new("inner");
+        inner_mod.add_fn(UMLFn::new("hello", "hello()"));
+        inner_mod.add_outer_entity(UMLOuterEntity::new("A", "super"));
+        inner_mod.add_relation(UMLRelation::new("hello", "A", UMLRelationKind::UMLDependency));
+        outer_mod.add_struct(UMLClass::new("A", vec![], vec![], UMLClassKind::UMLClass));
+        outer_mod.add_module(inner_mod);
+        uml_graph.add_fn(UMLFn::new("main", "main()"));
+        uml_graph.add_module(outer_mod);
+
+        let dot_string = uml_graph.to_string();
+        let target_string = 
+r#"digraph ast {
+    subgraph cluster_outer {
+        label="outer";
+        "outer.A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR></TABLE>>][margin="0"][shape="box"];
+        subgraph cluster_outer_inner {
+            label="inner";
+            "outer.inner.hello"[label="hello"];
+            "outer.inner.hello" -> "outer.A"[label=""][style="dashed"][arrowhead="vee"];
+        }
+    }
+    "main"[label="main"];
+}
+"#;
+        assert_eq!(dot_string, target_string);
+    }
+
+    #[test]
+    fn test_escaped_attributes_to_dot() {
+        let mut uml_graph = UMLGraph::new("");
+        uml_graph.doc = Some(String::from("The \"main\" crate."));
+        let mut main = UMLFn::new("main", "main()");
+        main.doc = Some(String::from("Says \"hi\"]."));
+        uml_graph.add_fn(main);
+        uml_graph.add_struct(UMLClass::new("Tree", vec![], vec![], UMLClassKind::UMLClass));
+        let mut relation = UMLRelation::new("Tree", "Tree", UMLRelationKind::UMLAssociationUni);
+        relation.set_role("\"root\"");
+        uml_graph.add_relation(relation);
+        let options = ExportOptions { doc_notes: true, ..Default::default() };
+
+        // quotes in attributes stay inside of them, notes get an id no item can have
+        let dot_string = uml_graph.to_string_with_options(&options);
+        let target_string = r#"digraph ast {
+    tooltip="The \"main\" crate.";
+    "Tree"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Tree</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "main"[label="main"][tooltip="Says \"hi\"]."];
+    "main__doc"[label="Says \"hi\"].\l"][shape="note"];
+    "main__doc" -> "main"[label=""][style="dashed"][arrowhead="none"];
+    "Tree" -> "Tree"[label=""][headlabel="\"root\""][arrowhead="vee"];
+}
 "#;
         assert_eq!(dot_string, target_string);
     }
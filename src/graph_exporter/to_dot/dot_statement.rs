@@ -0,0 +1,150 @@
+use dot_graph::{Style, Arrow};
+use super::escape_dot_string;
+
+// node and edge statements with the extra attributes dot_graph can't write, e.g. tooltips and end labels
+// labels are written as given, the values of the other attributes are escaped
+#[derive(Clone)]
+pub(super) struct Node {
+    pub name: String,
+    label: String,
+    // HTML-like labels are enclosed in angle brackets instead of quotes
+    is_html_label: bool,
+    attrs: Vec<(String, String)>,
+    style: Style,
+    color: Option<String>,
+    shape: Option<String>
+}
+
+impl Node {
+    pub fn new(name: &str) -> Self {
+        Node { name: String::from(name), label: String::from(name), is_html_label: false, attrs: vec![], style: Style::None, color: None, shape: None }
+    }
+
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = String::from(label);
+        self
+    }
+
+    pub fn html_label(mut self, label: &str) -> Self {
+        self.label = String::from(label);
+        self.is_html_label = true;
+        self
+    }
+
+    pub fn attr(mut self, name: &str, value: &str) -> Self {
+        self.attrs.push((String::from(name), String::from(value)));
+        self
+    }
+
+    pub fn tooltip(self, tooltip: Option<&str>) -> Self {
+        match tooltip {
+            Some(t) => self.attr("tooltip", t),
+            None => self
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn color(mut self, color: Option<&str>) -> Self {
+        self.color = color.map(String::from);
+        self
+    }
+
+    pub fn shape(mut self, shape: Option<&str>) -> Self {
+        self.shape = shape.map(String::from);
+        self
+    }
+
+    pub fn to_dot_string(&self) -> String {
+        let mut text = match self.is_html_label {
+            true => format!("\"{}\"[label=<{}>]", self.name, self.label),
+            false => format!("\"{}\"[label=\"{}\"]", self.name, self.label)
+        };
+        text.push_str(&get_attrs_text(&self.attrs));
+        text.push_str(&get_style_text(&self.style, self.color.as_deref()));
+        if let Some(s) = &self.shape {
+            text.push_str(&format!("[shape=\"{}\"]", s));
+        }
+        text.push(';');
+        text
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Edge {
+    pub from: String,
+    pub to: String,
+    label: String,
+    attrs: Vec<(String, String)>,
+    style: Style,
+    start_arrow: Arrow,
+    end_arrow: Arrow
+}
+
+impl Edge {
+    pub fn new(from: &str, to: &str, label: &str) -> Self {
+        Edge { from: String::from(from), to: String::from(to), label: String::from(label), attrs: vec![], style: Style::None, start_arrow: Arrow::default(), end_arrow: Arrow::default() }
+    }
+
+    pub fn attr(mut self, name: &str, value: &str) -> Self {
+        self.attrs.push((String::from(name), String::from(value)));
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn start_arrow(mut self, arrow: Arrow) -> Self {
+        self.start_arrow = arrow;
+        self
+    }
+
+    pub fn end_arrow(mut self, arrow: Arrow) -> Self {
+        self.end_arrow = arrow;
+        self
+    }
+
+    pub fn to_dot_string(&self) -> String {
+        let mut text = format!("\"{}\" -> \"{}\"[label=\"{}\"]", self.from, self.to, self.label);
+        text.push_str(&get_attrs_text(&self.attrs));
+        text.push_str(&get_style_text(&self.style, None));
+        let mut arrow_text: Vec<String> = vec![];
+        if !self.end_arrow.is_default() {
+            arrow_text.push(format!("arrowhead=\"{}\"", self.end_arrow.to_dot_string()));
+        }
+        if !self.start_arrow.is_default() {
+            arrow_text.push(format!("arrowtail=\"{}\"", self.start_arrow.to_dot_string()));
+        }
+        if !self.start_arrow.is_default() && !self.end_arrow.is_default() {
+            arrow_text.push(String::from("dir=\"both\""));
+        }
+        if !arrow_text.is_empty() {
+            text.push_str(&["[", &arrow_text.join(" "), "]"].concat());
+        }
+        text.push(';');
+        text
+    }
+}
+
+fn get_attrs_text(attrs: &[(String, String)]) -> String {
+    attrs
+        .iter()
+        .map(|(name, value)| format!("[{}=\"{}\"]", name, escape_dot_string(value)))
+        .collect()
+}
+
+fn get_style_text(style: &Style, color: Option<&str>) -> String {
+    let mut text = String::new();
+    if *style != Style::None {
+        text.push_str(&format!("[style=\"{}\"]", style.as_slice()));
+    }
+    if let Some(c) = color {
+        text.push_str(&format!("[color=\"{}\"]", c));
+    }
+    text
+}
@@ -1,54 +1,185 @@
-use super::{HasDotEntity, DotEntity};
+use super::{HasDotEntity, DotEntity, ExportOptions, DeriveStyle, UNSAFE_COLOR, get_doc_note_entities, get_qualifier_text, escape_html_text};
 use crate::uml_entity::*;
-use dot_graph::{Node};
+use dot_graph::Style;
+use super::dot_statement::Node;
+
+// line breaks of HTML-like labels, justifying the line they end
+const LEFT_BREAK: &str = r#"<BR ALIGN="LEFT"/>"#;
+const RIGHT_BREAK: &str = r#"<BR ALIGN="RIGHT"/>"#;
 
 impl HasDotEntity for UMLClass {
-    fn get_dot_entities(&self, name_prefix: &str) -> Vec<DotEntity> {
+    fn get_dot_entities(&self, name_prefix: &str, options: &ExportOptions) -> Vec<DotEntity> {
         let mut dot_entities = vec![];
-        match self.kind {
+        let method_names: Vec<String> = self.get_methods()
+            .iter()
+            .filter(|m| !options.public_only || m.visibility.is_public())
+            .map(|m| get_method_text(m, get_self_name(self, options), true))
+            .collect();
+        let field_names: Vec<String> = self.get_fields()
+            .iter()
+            .filter(|f| !options.public_only || f.visibility.is_public())
+            .map(|f| escape_html_text(&[get_visibility_marker(&f.visibility), &f.full_name].concat()))
+            .collect();
+
+        // header and compartments differ by class kind, empty compartments are omitted
+        let (mut header, mut compartments): (String, Vec<Vec<String>>) = match &self.kind {
             UMLClassKind::UMLClass => {
-                let mut label_text: Vec<&str> = vec![&self.name];
-                let method_names = self.get_method_names();
-                let field_names = self.get_field_names();
-
-                let method_names_str = method_names.join(r"\l");
-                let field_names_str = field_names.join(r"\l");
-                if method_names.len() + field_names.len() > 0 {
-                    label_text.insert(0, "{");
-                    if field_names.len() > 0 {
-                        label_text.push("|");
-                        label_text.push(&field_names_str);
-                    } 
-                    if method_names.len() > 0 {
-                        label_text.push("|");
-                        label_text.push(&method_names_str);    
-                    }
-                    label_text.push("}");
-                }
-                let label: String = label_text.into_iter().collect();
-                
-                let name = vec![name_prefix, &self.name];
-                dot_entities.push(DotEntity::Node(Node::new(&name.concat()).label(&label).shape(Some("record"))));
-                
+                (escape_html_text(&self.name), vec![field_names, method_names])
             },
             UMLClassKind::UMLTrait => {
-                let mut label_text: Vec<&str> = vec![r"Interface\l", &self.name];
-                let method_names = self.get_method_names();
-
-                let method_names_str = method_names.join(r"\l");
-                if method_names.len() > 0 {
-                    label_text.insert(0, "{");
-                    label_text.push("|");
-                    label_text.push(&method_names_str);
-                    label_text.push("}");
-                }
-                let label: String = label_text.into_iter().collect();
-                
-                let name = vec![name_prefix, &self.name];
-                dot_entities.push(DotEntity::Node(Node::new(&name.concat()).label(&label).shape(Some("record"))));
+                (["Interface", LEFT_BREAK, &escape_html_text(&self.name)].concat(), vec![field_names, method_names])
+            },
+            UMLClassKind::UMLEnum => {
+                let variant_names: Vec<String> = self.get_variant_names()
+                    .iter()
+                    .map(|v| escape_html_text(v))
+                    .collect();
+                (["&lt;&lt;enumeration&gt;&gt;", LEFT_BREAK, &escape_html_text(&self.name)].concat(), vec![variant_names, method_names])
             },
+            UMLClassKind::UMLBlanket(self_ty) => {
+                (escape_html_text(&get_blanket_header(self_ty, &self.get_generic_names())), vec![field_names, method_names])
+            },
+        };
+
+        // one compartment per implemented trait, or a single one with only the traits' names when collapsed
+        let trait_methods = self.get_trait_methods();
+        match options.collapse_trait_impls {
+            true => compartments.push(trait_methods.iter().map(|(t, _)| escape_html_text(t)).collect()),
+            false => compartments.extend(
+                trait_methods.iter().map(|(t, methods)| {
+                    methods
+                        .iter()
+                        .map(|m| [&escape_html_text(t), ": ", &get_method_text(m, get_self_name(self, options), false)].concat())
+                        .collect()
+                })
+            )
+        }
+
+        // derived traits as a stereotype, unless they are drawn as realizations
+        let derive_names = self.get_derive_names();
+        if options.derive_style == DeriveStyle::Stereotype && !derive_names.is_empty() {
+            header = ["&lt;&lt;derive: ", &escape_html_text(&derive_names.join(", ")), "&gt;&gt;", LEFT_BREAK, &header].concat();
+        }
+
+        // template parameters go right-justified above the name, like the dashed box on the class' corner
+        let generic_names = self.get_generic_names();
+        if !generic_names.is_empty() && !matches!(self.kind, UMLClassKind::UMLBlanket(_)) {
+            header = [&escape_html_text(&generic_names.join(", ")), RIGHT_BREAK, &header].concat();
         }
 
+        // a table with the header and the compartments' left-justified lines, the node's box is its border
+        let mut rows: Vec<String> = vec![["<TR><TD>", &header, "</TD></TR>"].concat()];
+        compartments
+            .iter()
+            .filter(|c| !c.is_empty())
+            .for_each(|c| rows.push([r#"<TR><TD ALIGN="LEFT">"#, &c.join(LEFT_BREAK), LEFT_BREAK, "</TD></TR>"].concat()));
+        let label = [r#"<TABLE BORDER="0" CELLSPACING="0">"#, &rows.join("<HR/>"), "</TABLE>"].concat();
+
+        let name = [name_prefix, &self.name].concat();
+        let doc = get_class_doc_text(self, options);
+        let has_unsafe_method = self.get_methods()
+            .iter()
+            .any(|m| m.is_unsafe() && (!options.public_only || m.visibility.is_public()))
+            || trait_methods.iter().flat_map(|(_, methods)| methods).any(|m| m.is_unsafe());
+        let color = match options.highlight_unsafe && has_unsafe_method {
+            true => Some(UNSAFE_COLOR),
+            false => None
+        };
+        // blanket impls' template nodes are no real types
+        let style = match self.kind {
+            UMLClassKind::UMLBlanket(_) => Style::Dashed,
+            _ => Style::None
+        };
+        dot_entities.push(DotEntity::Node(Node::new(&name).html_label(&label).tooltip(doc.as_deref()).attr("margin", "0").shape(Some("box")).style(style).color(color)));
+        if let (true, Some(d)) = (options.doc_notes, &doc) {
+            dot_entities.append(&mut get_doc_note_entities(&name, d));
+        }
         dot_entities
     }
-}
\ No newline at end of file
+}
+
+fn get_class_doc_text(class: &UMLClass, options: &ExportOptions) -> Option<String> {
+    // members' docs follow the class' own one, each after the member's name
+    let mut doc_lines: Vec<String> = class.doc.iter().cloned().collect();
+    class.get_fields()
+        .iter()
+        .filter(|f| !options.public_only || f.visibility.is_public())
+        .for_each(|f| doc_lines.extend(f.doc.iter().map(|d| format!("{}: {}", f.name, d))));
+    class.get_methods()
+        .iter()
+        .filter(|m| !options.public_only || m.visibility.is_public())
+        .for_each(|m| doc_lines.extend(m.doc.iter().map(|d| format!("{}(): {}", m.name, d))));
+    match doc_lines.is_empty() {
+        true => None,
+        false => Some(doc_lines.join("\n"))
+    }
+}
+
+fn get_blanket_header(self_ty: &str, generic_names: &[String]) -> String {
+    // "any T: Display" for the param itself, "any &T where T: Named" for a type made of it
+    let generics = generic_names.join(", ");
+    let is_param = generic_names.iter().any(|g| g.split(':').next().map(str::trim) == Some(self_ty));
+    match (is_param, generic_names.iter().any(|g| g.contains(':'))) {
+        (true, _) => ["any ", &generics].concat(),
+        (false, true) => ["any ", self_ty, " where ", &generics].concat(),
+        (false, false) => ["any ", self_ty].concat()
+    }
+}
+
+fn get_method_text(method: &UMLFn, self_name: Option<&str>, show_visibility: bool) -> String {
+    // provided trait methods are marked to tell them from required ones
+    let visibility_marker = match show_visibility {
+        true => get_visibility_marker(&method.visibility),
+        false => ""
+    };
+    let full_name = match self_name {
+        Some(name) => replace_self(&method.full_name, name),
+        None => method.full_name.clone()
+    };
+    // associated functions are static, which UML underlines
+    let feature_text = escape_html_text(&[visibility_marker, &full_name].concat());
+    let feature_text = match method.is_associated() {
+        true => ["<U>", &feature_text, "</U>"].concat(),
+        false => feature_text
+    };
+    let qualifier_text = escape_html_text(&get_qualifier_text(&method.qualifiers));
+    match method.has_default {
+        true => ["«default» ", &qualifier_text, &feature_text].concat(),
+        false => [qualifier_text, feature_text].concat()
+    }
+}
+
+fn get_visibility_marker(visibility: &UMLVisibility) -> &'static str {
+    match visibility {
+        UMLVisibility::Public => "+",
+        UMLVisibility::Crate => "~",
+        UMLVisibility::Restricted => "#",
+        UMLVisibility::Private => "-",
+    }
+}
+
+fn get_self_name<'a>(class: &'a UMLClass, options: &ExportOptions) -> Option<&'a str> {
+    // blanket impls' `Self` is any type
+    match (options.resolve_self, &class.kind) {
+        (true, UMLClassKind::UMLBlanket(_)) | (false, _) => None,
+        (true, _) => Some(&class.name)
+    }
+}
+
+fn replace_self(text: &str, self_name: &str) -> String {
+    // whole `Self` words only, `&self` and `SelfRef` stay
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(idx) = rest.find("Self") {
+        let is_word_start = !rest[..idx].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+        let is_word_end = !rest[idx + 4..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        result.push_str(&rest[..idx]);
+        match is_word_start && is_word_end {
+            true => result.push_str(self_name),
+            false => result.push_str("Self")
+        }
+        rest = &rest[idx + 4..];
+    }
+    result.push_str(rest);
+    result
+}
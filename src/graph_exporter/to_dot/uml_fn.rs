@@ -1,12 +1,20 @@
-use super::{HasDotEntity, DotEntity};
+use super::{HasDotEntity, DotEntity, ExportOptions, UNSAFE_COLOR, get_doc_note_entities, get_qualifier_text, escape_dot_string};
 use crate::uml_entity::*;
-use dot_graph::{Node};
+use super::dot_statement::Node;
 
 impl HasDotEntity for UMLFn {
-    fn get_dot_entities(&self, name_prefix: &str) -> Vec<DotEntity> {
+    fn get_dot_entities(&self, name_prefix: &str, options: &ExportOptions) -> Vec<DotEntity> {
         let mut dot_entities = vec![];
-        let name = vec![name_prefix, &self.name];
-        dot_entities.push(DotEntity::Node(Node::new(&name.concat()).label(&self.name)));
+        let name = [name_prefix, &self.name].concat();
+        let label = escape_dot_string(&[get_qualifier_text(&self.qualifiers), self.name.clone()].concat());
+        let color = match options.highlight_unsafe && self.is_unsafe() {
+            true => Some(UNSAFE_COLOR),
+            false => None
+        };
+        dot_entities.push(DotEntity::Node(Node::new(&name).label(&label).tooltip(self.doc.as_deref()).color(color)));
+        if let (true, Some(d)) = (options.doc_notes, &self.doc) {
+            dot_entities.append(&mut get_doc_note_entities(&name, d));
+        }
         dot_entities
     }
 }
\ No newline at end of file
@@ -1,23 +1,199 @@
-use super::{HasDotEntity, DotEntity};
+use super::{HasDotEntity, DotEntity, ExportOptions, DeriveStyle, STUB_COLOR};
+use super::uml_relation::get_dot_node_name;
 use crate::uml_entity::*;
+use dot_graph::Style;
+use super::dot_statement::Node;
 
 impl HasDotEntity for UMLGraph {
-    fn get_dot_entities(&self, name_prefix: &str) -> Vec<DotEntity> {
+    fn get_dot_entities(&self, name_prefix: &str, options: &ExportOptions) -> Vec<DotEntity> {
         let mut dot_entities = vec![];
+        // non-public items are hidden together with their relations if only public ones are wanted
+        let mut hidden_names: Vec<&String> = vec![];
+        if options.public_only {
+            self.structs()
+                .iter()
+                .filter(|st| !st.visibility.is_public())
+                .for_each(|st| hidden_names.push(&st.name));
+            self.fns()
+                .iter()
+                .filter(|f| !f.visibility.is_public())
+                .for_each(|f| hidden_names.push(&f.name));
+            self.macros()
+                .iter()
+                .filter(|m| !m.visibility.is_public())
+                .for_each(|m| hidden_names.push(&m.name));
+            self.statics()
+                .iter()
+                .filter(|s| !s.visibility.is_public())
+                .for_each(|s| hidden_names.push(&s.name));
+        }
+
         self.structs()
             .iter()
-            .for_each(|st| dot_entities.append(&mut st.get_dot_entities(name_prefix)));
+            .filter(|st| !hidden_names.contains(&&st.name))
+            .for_each(|st| dot_entities.append(&mut st.get_dot_entities(name_prefix, options)));
         self.fns()
             .iter()
-            .for_each(|f| dot_entities.append(&mut f.get_dot_entities(name_prefix)));
+            .filter(|f| !hidden_names.contains(&&f.name))
+            .for_each(|f| dot_entities.append(&mut f.get_dot_entities(name_prefix, options)));
+        self.macros()
+            .iter()
+            .filter(|m| !hidden_names.contains(&&m.name))
+            .for_each(|m| dot_entities.append(&mut m.get_dot_entities(name_prefix, options)));
+        self.statics()
+            .iter()
+            .filter(|s| !hidden_names.contains(&&s.name))
+            .for_each(|s| dot_entities.append(&mut s.get_dot_entities(name_prefix, options)));
+        // extern crates are only named, like packages folded up
+        self.extern_crates()
+            .iter()
+            .for_each(|c| dot_entities.push(DotEntity::Node(
+                Node::new(&[name_prefix, "extern_crate_", &c.replace(' ', "_")].concat())
+                    .label(&[r"<<extern crate>>\n", c].concat())
+                    .shape(Some("folder"))
+            )));
         self.relations()
             .iter()
-            .for_each(|r| dot_entities.append(&mut r.get_dot_entities(name_prefix)));
+            .filter(|r| !hidden_names.contains(&&r.from) && !hidden_names.contains(&&r.to))
+            .for_each(|r| dot_entities.append(&mut r.get_dot_entities(name_prefix, options)));
+        self.module_relations()
+            .iter()
+            .filter(|r| !hidden_names.contains(&&r.from))
+            .for_each(|r| dot_entities.append(&mut r.get_dot_entities(name_prefix, options)));
+        // imported items of crates whose stubs are not shown are left out along with them
+        // the edges to the stubs are shared, as the stubs are
         self.outer_relations()
             .iter()
+            .filter(|r| !hidden_names.contains(&&r.from))
+            .filter(|r| !options.external_stubs || !self.is_external_path(&r.to) || is_stub_crate_shown(&r.to, options))
             .for_each(|r| {
-                dot_entities.append(&mut r.get_dot_entities(name_prefix))
+                let is_shared = options.external_stubs && self.is_external_path(&r.to);
+                dot_entities.extend(
+                    r.get_dot_entities(name_prefix, options)
+                        .into_iter()
+                        .map(|e| if is_shared { e.into_shared() } else { e })
+                )
             });
+        if options.external_stubs {
+            dot_entities.append(&mut get_stub_dot_entities(self, name_prefix, options, &hidden_names));
+        }
+        if options.std_macros {
+            dot_entities.append(&mut get_std_macro_dot_entities(self, name_prefix, options, &hidden_names));
+        }
+        if options.derive_style == DeriveStyle::Realization {
+            dot_entities.append(&mut get_derive_dot_entities(self, name_prefix, options, &hidden_names));
+        }
         dot_entities
     }
-}
\ No newline at end of file
+}
+
+fn get_derive_dot_entities(uml_graph: &UMLGraph, name_prefix: &str, options: &ExportOptions, hidden_names: &[&String]) -> Vec<DotEntity> {
+    // derived traits are realized, traits not in the graph get a stub node each
+    // a full path names the same trait in every module, bare names are resolved in the module only
+    let mut dot_entities = vec![];
+    let mut stub_names: Vec<String> = vec![];
+    for st in uml_graph.structs().iter().filter(|st| !hidden_names.contains(&&st.name)) {
+        for derive_name in st.get_derive_names() {
+            let trait_name = derive_name.replace("::", ".");
+            // paths relative to the crate name its own traits, which have nodes of their own
+            let is_relative = ["crate.", "self.", "super."].iter().any(|p| trait_name.starts_with(p));
+            let is_shared = derive_name.contains("::") && !is_relative;
+            if !is_relative && !uml_graph.structs().iter().any(|other| other.name == trait_name) && !stub_names.contains(&derive_name) {
+                stub_names.push(derive_name);
+            }
+            let realization = UMLRelation::new(&st.name, &trait_name, UMLRelationKind::UMLRealization);
+            dot_entities.extend(
+                realization
+                    .get_dot_entities(name_prefix, options)
+                    .into_iter()
+                    .map(|e| if is_shared { e.into_shared() } else { e })
+            );
+        }
+    }
+    for stub_name in stub_names {
+        let label = [r"Interface\l", &stub_name].concat();
+        let node = DotEntity::Node(
+            Node::new(&get_dot_node_name(&stub_name.replace("::", "."), name_prefix))
+                .label(&label)
+                .shape(Some("record"))
+                .style(Style::Dashed)
+        );
+        match stub_name.contains("::") {
+            true => dot_entities.push(node.into_shared()),
+            false => dot_entities.push(node)
+        }
+    }
+    dot_entities
+}
+
+fn get_stub_dot_entities(uml_graph: &UMLGraph, name_prefix: &str, options: &ExportOptions, hidden_names: &[&String]) -> Vec<DotEntity> {
+    // relations to other crates' items, which get a stub node each, labelled with the full path
+    // the stubs are declared once for all modules
+    let mut dot_entities = vec![];
+    let external_relations: Vec<UMLRelation> = uml_graph.external_relations()
+        .into_iter()
+        .filter(|r| !hidden_names.contains(&&r.from) && is_stub_crate_shown(&r.to, options))
+        .collect();
+    external_relations
+        .iter()
+        .for_each(|r| dot_entities.extend(r.get_dot_entities(name_prefix, options).into_iter().map(DotEntity::into_shared)));
+
+    let outer_relations: Vec<UMLRelation> = uml_graph.outer_relations()
+        .into_iter()
+        .filter(|r| !hidden_names.contains(&&r.from) && uml_graph.is_external_path(&r.to) && is_stub_crate_shown(&r.to, options))
+        .collect();
+    let mut stub_names: Vec<(&String, bool)> = vec![];
+    for rel in external_relations.iter().chain(outer_relations.iter()) {
+        // targets of realizations, generalizations and interface usages are traits
+        let is_trait = matches!(rel.kind, UMLRelationKind::UMLRealization | UMLRelationKind::UMLGeneralization | UMLRelationKind::UMLInterfaceUsage);
+        match stub_names.iter_mut().find(|(n, _)| *n == &rel.to) {
+            Some((_, stub_is_trait)) => *stub_is_trait |= is_trait,
+            None => stub_names.push((&rel.to, is_trait))
+        }
+    }
+    for (stub_name, is_trait) in stub_names {
+        let path = stub_name.replace('.', "::");
+        let label = match is_trait {
+            true => [r"Interface\l", &path].concat(),
+            false => path
+        };
+        dot_entities.push(DotEntity::SharedNode(
+            Node::new(&get_dot_node_name(stub_name, name_prefix))
+                .label(&label)
+                .shape(Some("record"))
+                .style(Style::Dashed)
+                .color(Some(STUB_COLOR))
+        ));
+    }
+    dot_entities
+}
+
+fn get_std_macro_dot_entities(uml_graph: &UMLGraph, name_prefix: &str, options: &ExportOptions, hidden_names: &[&String]) -> Vec<DotEntity> {
+    // dependencies on std macros, which get a stub node each like the items of other crates
+    let mut dot_entities = vec![];
+    let mut stub_names: Vec<&String> = vec![];
+    for rel in uml_graph.std_macro_relations().into_iter().filter(|r| !hidden_names.contains(&&r.from)) {
+        dot_entities.extend(rel.get_dot_entities(name_prefix, options).into_iter().map(DotEntity::into_shared));
+        if !stub_names.contains(&&rel.to) {
+            stub_names.push(&rel.to);
+        }
+    }
+    for stub_name in stub_names {
+        let label = [r"\<\<macro\>\>\l", &stub_name.replace('.', "::"), "!"].concat();
+        dot_entities.push(DotEntity::SharedNode(
+            Node::new(stub_name)
+                .label(&label)
+                .shape(Some("record"))
+                .style(Style::Dashed)
+                .color(Some(STUB_COLOR))
+        ));
+    }
+    dot_entities
+}
+
+fn is_stub_crate_shown(path: &str, options: &ExportOptions) -> bool {
+    // the crate is the first segment of the full path
+    let crate_name = path.split('.').next().unwrap_or_default();
+    (options.stub_crates_allowed.is_empty() || options.stub_crates_allowed.iter().any(|c| c == crate_name))
+        && !options.stub_crates_denied.iter().any(|c| c == crate_name)
+}
@@ -0,0 +1,23 @@
+use super::{HasDotEntity, DotEntity, ExportOptions, get_doc_note_entities, escape_record_text};
+use crate::uml_entity::*;
+use super::dot_statement::Node;
+
+impl HasDotEntity for UMLMacro {
+    fn get_dot_entities(&self, name_prefix: &str, options: &ExportOptions) -> Vec<DotEntity> {
+        let mut dot_entities = vec![];
+        // macro box has its rules' matchers as the only compartment
+        let header = [r"\<\<macro\>\>\l", &self.name, "!"].concat();
+        let rules: Vec<String> = self.rules.iter().map(|r| escape_record_text(r)).collect();
+        let label = match rules.is_empty() {
+            true => header,
+            false => ["{", &header, "|", &rules.join(r"\l"), "}"].concat()
+        };
+
+        let name = [name_prefix, &self.name].concat();
+        dot_entities.push(DotEntity::Node(Node::new(&name).label(&label).tooltip(self.doc.as_deref()).shape(Some("record"))));
+        if let (true, Some(d)) = (options.doc_notes, &self.doc) {
+            dot_entities.append(&mut get_doc_note_entities(&name, d));
+        }
+        dot_entities
+    }
+}
@@ -1,75 +1,99 @@
-use super::{HasDotEntity, DotEntity};
+use super::{HasDotEntity, DotEntity, ExportOptions};
+use super::dot_statement::Edge;
 use crate::uml_entity::*;
-use dot_graph::{Edge, Style, Arrow, ArrowShape, Fill, Side};
+use dot_graph::{Style, Arrow, ArrowShape, Fill, Side};
 
 impl HasDotEntity for UMLRelation {
-    fn get_dot_entities(&self, name_prefix: &str) -> Vec<DotEntity> {
-        let from: String;
-        let to: String;
-
-        // ugly impletation, should be removed once refactoring done
-        // TODO
-        if !self.from.contains(".") {
-            from = vec![name_prefix, &self.from].concat();
-        } else {
-            from = self.from.clone();
-        }
-        if !self.to.contains(".") {
-            to = vec![name_prefix, &self.to].concat();
-        } else {
-            to = self.to.clone();
-        }
-        match self.kind {
+    fn get_dot_entities(&self, name_prefix: &str, _options: &ExportOptions) -> Vec<DotEntity> {
+        let from: String = get_dot_node_name(&self.from, name_prefix);
+        let to: String = get_dot_node_name(&self.to, name_prefix);
+        let edge = match self.kind {
             UMLRelationKind::UMLAggregation => {
-                vec![DotEntity::Edge(Edge::new(
-                    &from, 
-                    &to, 
-                    "")
-                    .start_arrow(Arrow::from_arrow(ArrowShape::Diamond(Fill::Open, Side::Both))),
-                )]
+                Edge::new(&from, &to, "")
+                    .start_arrow(Arrow::from_arrow(ArrowShape::Diamond(Fill::Open, Side::Both)))
             },
             UMLRelationKind::UMLComposition => {
-                vec![DotEntity::Edge(Edge::new(
-                    &from, 
-                    &to, 
-                    "")
+                Edge::new(&from, &to, "")
                     .end_arrow(Arrow::from_arrow(ArrowShape::diamond()))
-                )]
             },
             UMLRelationKind::UMLDependency => {
-                vec![DotEntity::Edge(Edge::new(
-                    &from,
-                    &to, 
-                    "")
+                Edge::new(&from, &to, "")
+                    .style(Style::Dashed)
+                    .end_arrow(Arrow::from_arrow(ArrowShape::vee()))
+            },
+            UMLRelationKind::UMLInterfaceUsage => {
+                // stereotyped dependency, with the associated type bindings
+                let usage_text = match self.bindings.is_empty() {
+                    true => String::from("«use»"),
+                    false => format!("«use» {}", self.bindings.join(", "))
+                };
+                Edge::new(&from, &to, &usage_text)
                     .style(Style::Dashed)
                     .end_arrow(Arrow::from_arrow(ArrowShape::vee()))
-                )]
             },
             UMLRelationKind::UMLAssociationUni => {
-                vec![DotEntity::Edge(Edge::new(
-                    &from,
-                    &to,
-                    "")
+                Edge::new(&from, &to, "")
                     .end_arrow(Arrow::from_arrow(ArrowShape::vee()))
-                )]
             },
             UMLRelationKind::UMLAssociationBi => {
-                vec![DotEntity::Edge(Edge::new(
-                    &from, 
-                    &to, 
-                    "")
+                Edge::new(&from, &to, "")
                     .end_arrow(Arrow::none())
-                )]
             },
             UMLRelationKind::UMLRealization => {
-                vec![DotEntity::Edge(Edge::new(
-                    &from, 
-                    &to, 
-                    "")
+                Edge::new(&from, &to, "")
+                    .end_arrow(Arrow::from_arrow(ArrowShape::Normal(Fill::Open, Side::Both)))
+                    .style(Style::Dashed)
+            },
+            UMLRelationKind::UMLGeneralization => {
+                Edge::new(&from, &to, "")
                     .end_arrow(Arrow::from_arrow(ArrowShape::Normal(Fill::Open, Side::Both)))
-                    .style(Style::Dashed),
-                )]
             },
-        }
+        };
+        vec![DotEntity::Edge(with_end_labels(edge, self))]
+    }
+}
+
+fn with_end_labels(edge: Edge, rel: &UMLRelation) -> Edge {
+    // roles and multiplicities are written at the ends of the edge
+    let head = get_end_label(&rel.to_role, &rel.to_multiplicity);
+    let tail = get_end_label(&rel.from_role, &rel.from_multiplicity);
+    let mut edge = edge;
+    if !head.is_empty() {
+        edge = edge.attr("headlabel", &head);
+    }
+    if !tail.is_empty() {
+        edge = edge.attr("taillabel", &tail);
+    }
+    edge
+}
+
+fn get_end_label(role: &Option<String>, multiplicity: &Option<String>) -> String {
+    // role on top of the multiplicity
+    [role, multiplicity]
+        .iter()
+        .filter_map(|t| t.as_deref())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+pub(super) fn get_dot_node_name(name: &str, name_prefix: &str) -> String {
+    // ugly impletation, should be removed once refactoring done
+    // TODO
+    if !name.contains('.') {
+        return [name_prefix, name].concat()
+    }
+    // resolve relative module paths against the module the relation belongs to
+    if let Some(rest) = name.strip_prefix("crate.") {
+        String::from(rest)
+    } else if let Some(rest) = name.strip_prefix("self.") {
+        [name_prefix, rest].concat()
+    } else if let Some(rest) = name.strip_prefix("super.") {
+        let parent_prefix = match name_prefix.trim_end_matches('.').rfind('.') {
+            Some(idx) => &name_prefix[..idx + 1],
+            None => ""
+        };
+        get_dot_node_name(rest, parent_prefix)
+    } else {
+        String::from(name)
     }
-}
\ No newline at end of file
+}
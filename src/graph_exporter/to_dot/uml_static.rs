@@ -0,0 +1,17 @@
+use super::{HasDotEntity, DotEntity, ExportOptions, get_doc_note_entities, escape_dot_string};
+use crate::uml_entity::*;
+use super::dot_statement::Node;
+
+impl HasDotEntity for UMLStatic {
+    fn get_dot_entities(&self, name_prefix: &str, options: &ExportOptions) -> Vec<DotEntity> {
+        let mut dot_entities = vec![];
+        // statics are boxes, to tell them from fns
+        let name = [name_prefix, &self.name].concat();
+        let label = escape_dot_string(&self.full_name);
+        dot_entities.push(DotEntity::Node(Node::new(&name).label(&label).tooltip(self.doc.as_deref()).shape(Some("box"))));
+        if let (true, Some(d)) = (options.doc_notes, &self.doc) {
+            dot_entities.append(&mut get_doc_note_entities(&name, d));
+        }
+        dot_entities
+    }
+}
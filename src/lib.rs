@@ -3,8 +3,11 @@ mod parser;
 mod graph_exporter;
 mod file_reader;
 
-use uml_entity::{UMLGraph};
-use graph_exporter::{GraphExporter};
+pub use uml_entity::{
+    UMLGraph, UMLClass, UMLClassKind, UMLFn, UMLReceiver, UMLField, UMLVisibility, UMLQualifier,
+    UMLMacro, UMLStatic, UMLRelation, UMLRelationKind, UMLOuterEntity
+};
+pub use graph_exporter::{GraphExporter, ExportOptions, DeriveStyle};
 use std::path::Path;
 use std::fs::read_to_string;
 use parser::{ast_parser::AstParser, StringParser};
@@ -20,11 +23,16 @@ use file_reader::get_rs_file_paths;
 ///     let _ = rudg::rs2dot("src/lib.rs");
 /// }
 /// ```
-pub fn rs2dot<'a, P: AsRef<Path>>(path: P) -> String {
+pub fn rs2dot<P: AsRef<Path>>(path: P) -> String {
+    rs2dot_with_options(path, &ExportOptions::default())
+}
+
+/// Same as `rs2dot`, but exported according to the given `ExportOptions`.
+pub fn rs2dot_with_options<P: AsRef<Path>>(path: P, options: &ExportOptions) -> String {
     let p = path.as_ref();
     if p.is_file() {
         let file_string = read_to_string(path).unwrap();
-        code_to_dot_digraph(&file_string)
+        code_to_dot_digraph_with_options(&file_string, options)
     } else if p.is_dir() {
         let mut uml_graph = UMLGraph::new("");
         // parse every file as individual module inside the whole Graph
@@ -34,7 +42,7 @@ pub fn rs2dot<'a, P: AsRef<Path>>(path: P) -> String {
             uml_module.name = file_p.file_stem().unwrap().to_str().unwrap().to_string();
             uml_graph.add_module(uml_module);
         }
-        uml_graph.to_string()
+        uml_graph.to_string_with_options(options)
     } else {
         String::new()
     }
@@ -45,3 +53,20 @@ pub fn code_to_dot_digraph(code: &str) -> String {
     uml_graph.to_string()
 }
 
+pub fn code_to_dot_digraph_with_options(code: &str, options: &ExportOptions) -> String {
+    let uml_graph = AstParser::parse_string(code);
+    uml_graph.to_string_with_options(options)
+}
+
+/// The function `code_to_uml_graph` returns the UML graph parsed from the code,
+/// for callers that inspect or export it on their own.
+///
+/// # Examples
+/// ```
+/// let uml_graph = rudg::code_to_uml_graph("struct A; impl A { fn new() -> Self { A } }");
+/// let new = &uml_graph.structs()[0].get_methods()[0];
+/// assert!(new.is_associated());
+/// ```
+pub fn code_to_uml_graph(code: &str) -> UMLGraph {
+    AstParser::parse_string(code)
+}
@@ -1,4 +1,4 @@
-use rudg::rs2dot;
+use rudg::{rs2dot_with_options, ExportOptions, DeriveStyle};
 use std::{env, fs};
 use std::path::{Path, PathBuf};
 use clap::{arg, command};
@@ -13,12 +13,48 @@ fn main() {
             .required(false)
             .allow_invalid_utf8(true),
         )
+        .arg(arg!(--"public-only" "Hides non-public items and members"))
+        .arg(arg!(--"derive-realizations" "Draws derived traits as realizations instead of stereotypes"))
+        .arg(arg!(--"doc-notes" "Adds doc comments as notes next to the documented items"))
+        .arg(arg!(--"highlight-unsafe" "Colours unsafe functions and classes with unsafe methods"))
+        .arg(arg!(--"resolve-self" "Shows Self in method signatures as the type it stands for"))
+        .arg(arg!(--"collapse-trait-impls" "Lists only the names of implemented traits instead of their methods"))
+        .arg(arg!(--"external-stubs" "Draws stub nodes for traits and types of other crates"))
+        .arg(
+            arg!(
+                --"stub-crates" <CRATES> "Comma separated crates to draw stub nodes for, all by default"
+            )
+            .required(false),
+        )
+        .arg(
+            arg!(
+                --"hide-stub-crates" <CRATES> "Comma separated crates to never draw stub nodes for"
+            )
+            .required(false),
+        )
+        .arg(arg!(--"std-macros" "Draws dependencies on std macros like println! too"))
         .get_matches();
 
+    let options = ExportOptions {
+        public_only: matches.is_present("public-only"),
+        derive_style: match matches.is_present("derive-realizations") {
+            true => DeriveStyle::Realization,
+            false => DeriveStyle::Stereotype,
+        },
+        doc_notes: matches.is_present("doc-notes"),
+        highlight_unsafe: matches.is_present("highlight-unsafe"),
+        resolve_self: matches.is_present("resolve-self"),
+        collapse_trait_impls: matches.is_present("collapse-trait-impls"),
+        external_stubs: matches.is_present("external-stubs"),
+        stub_crates_allowed: get_crate_names(matches.value_of("stub-crates")),
+        stub_crates_denied: get_crate_names(matches.value_of("hide-stub-crates")),
+        std_macros: matches.is_present("std-macros"),
+    };
+
     // You can check the value provided by positional arguments, or option arguments
     if let Some(file) = matches.value_of("file") {
         let path = Path::new(file);
-        let results = rs2dot(path);
+        let results = rs2dot_with_options(path, &options);
         let mut target_name = PathBuf::new();
 
         if let Some(raw_config) = matches.value_of_os("output") {
@@ -38,4 +74,10 @@ fn main() {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+fn get_crate_names(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|v| v.split(',').map(|c| String::from(c.trim())).filter(|c| !c.is_empty()).collect())
+        .unwrap_or_default()
+}
@@ -1,9 +1,10 @@
 // The basic idea is, extend crates ra_ap_syntax, especially ast, to support UML entity methods.
-use ra_ap_syntax::{ast::{self, HasModuleItem}, SourceFile, Parse};
+use ra_ap_syntax::{ast::{self, AstNode, HasModuleItem, HasName}, SourceFile, Parse};
 
 use crate::uml_entity::*;
 use super::StringParser;
 use super::to_uml_entities::HasUMLEntity;
+use super::to_uml_entities::utils::{get_doc_text, get_doc_text_from_node};
 
 pub struct AstParser;
 
@@ -12,49 +13,135 @@ impl StringParser for AstParser {
         // parse code string into UML Graph
         let parse: Parse<SourceFile> = SourceFile::parse(input);
         let file: SourceFile = parse.tree();
-        let mut uml_graph = UMLGraph::new("");
-        let mut uml_entities: Vec<UMLEntity> = vec![];
-
-        // visit all items in SourceFile and extract dot entities from every type of them
-        for item in file.items() {
-            match item {
-                ast::Item::Fn(f) => {
-                    uml_entities.append(&mut f.get_uml_entities());
-                },
-                ast::Item::Impl(ip) => {
-                    uml_entities.append(&mut ip.get_uml_entities());
-                },
-                ast::Item::Struct(st) => {
-                    uml_entities.append(&mut st.get_uml_entities());
-                },
-                ast::Item::Trait(tt) => {
-                    uml_entities.append(&mut tt.get_uml_entities());
-                },
-                ast::Item::Use(u) => {
-                    uml_entities.append(&mut u.get_uml_entities());
+        let mut uml_graph = parse_items(file.items(), "");
+        uml_graph.doc = get_doc_text(&file);
+        uml_graph
+    }
+}
+
+fn parse_items(items: impl Iterator<Item = ast::Item>, name: &str) -> UMLGraph {
+    // parse items of a file or an inline module into UML Graph
+    let mut uml_graph = UMLGraph::new(name);
+    let mut uml_entities: Vec<UMLEntity> = vec![];
+
+    // visit all items and extract dot entities from every type of them
+    for item in items {
+        match item {
+            ast::Item::Fn(f) => {
+                uml_entities.append(&mut f.get_uml_entities());
+            },
+            ast::Item::Impl(ip) => {
+                uml_entities.append(&mut ip.get_uml_entities());
+            },
+            ast::Item::Struct(st) => {
+                uml_entities.append(&mut st.get_uml_entities());
+            },
+            ast::Item::Enum(en) => {
+                uml_entities.append(&mut en.get_uml_entities());
+            },
+            ast::Item::Trait(tt) => {
+                uml_entities.append(&mut tt.get_uml_entities());
+            },
+            ast::Item::MacroRules(mr) => {
+                uml_entities.append(&mut mr.get_uml_entities());
+            },
+            ast::Item::Use(u) => {
+                uml_entities.append(&mut u.get_uml_entities());
+            },
+            ast::Item::ExternBlock(eb) => {
+                // each extern block is a foreign package, calls to its fns are resolved into it
+                let foreign_module = parse_extern_block(&eb, &uml_graph);
+                let mod_name = ["self.", &foreign_module.name].concat();
+                foreign_module.fns()
+                    .iter()
+                    .for_each(|f| uml_entities.push(UMLEntity::UMLOuterEntity(UMLOuterEntity::new(&f.name, &mod_name))));
+                uml_graph.add_module(foreign_module);
+            },
+            ast::Item::ExternCrate(ec) => {
+                let crate_name = ec.name_ref().unwrap().text().to_string();
+                match ec.rename() {
+                    Some(r) => uml_graph.add_extern_crate(&format!("{} {}", crate_name, r)),
+                    None => uml_graph.add_extern_crate(&crate_name)
                 }
-                _ => (),
-            }
+            },
+            ast::Item::Module(m) => {
+                // only inline modules have their items here, `mod name;` is parsed from its own file
+                if let Some(item_list) = m.item_list() {
+                    let mut uml_module = parse_items(item_list.items(), m.name().unwrap().text().as_str());
+                    // module docs are either outside with `///` or inside the braces with `//!`
+                    let doc_texts: Vec<String> = [get_doc_text(&m), get_doc_text_from_node(item_list.syntax())]
+                        .into_iter()
+                        .flatten()
+                        .collect();
+                    if !doc_texts.is_empty() {
+                        uml_module.doc = Some(doc_texts.join("\n"));
+                    }
+                    uml_graph.add_module(uml_module);
+                }
+            },
+            _ => (),
         }
+    }
 
-        // add relations last
-        let mut relations: Vec<UMLRelation> = vec![];
-        for e in uml_entities {
-            match e {
-                UMLEntity::UMLClass(c) => uml_graph.add_struct(c),
-                UMLEntity::UMLFn(f) => uml_graph.add_fn(f),
-                UMLEntity::UMLRelation(r) => {
-                    // uml_graph.add_relation(r);
-                    relations.push(r);
-                },
-                UMLEntity::UMLOuterEntity(oe) => uml_graph.add_outer_entity(oe),
-            }
-        }
-        for rel in relations {
-            uml_graph.add_relation(rel);
+    add_uml_entities(&mut uml_graph, uml_entities);
+    uml_graph
+}
+
+fn parse_extern_block(eb: &ast::ExternBlock, parent_graph: &UMLGraph) -> UMLGraph {
+    // named after the ABI, numbered if there are more blocks with the same one
+    let abi_name = eb.abi()
+        .map(|abi| abi.to_string().trim_start_matches("extern").trim().trim_matches('"').to_string())
+        .filter(|a| !a.is_empty())
+        .unwrap_or_else(|| String::from("C"));
+    let base_name = format!("extern_{}", abi_name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+    let mut name = base_name.clone();
+    let mut idx = 1;
+    while parent_graph.modules.contains_key(&name) {
+        name = format!("{}_{}", base_name, idx);
+        idx += 1;
+    }
+
+    let mut uml_graph = UMLGraph::new(&name);
+    uml_graph.stereotype = Some(String::from("foreign"));
+    let mut uml_entities: Vec<UMLEntity> = vec![];
+    for item in eb.extern_item_list().iter().flat_map(|eil| eil.extern_items()) {
+        match item {
+            ast::ExternItem::Fn(f) => {
+                uml_entities.append(&mut f.get_uml_entities());
+            },
+            ast::ExternItem::Static(s) => {
+                uml_entities.append(&mut s.get_uml_entities());
+            },
+            _ => (),
         }
+    }
+    add_uml_entities(&mut uml_graph, uml_entities);
+    uml_graph
+}
 
-        uml_graph
+fn add_uml_entities(uml_graph: &mut UMLGraph, uml_entities: Vec<UMLEntity>) {
+    // add trait impls once all types are known, and relations last
+    let mut trait_impls: Vec<UMLClass> = vec![];
+    let mut relations: Vec<UMLRelation> = vec![];
+    for e in uml_entities {
+        match e {
+            UMLEntity::UMLClass(c) if c.is_trait_impl_only() => trait_impls.push(c),
+            UMLEntity::UMLClass(c) => uml_graph.add_struct(c),
+            UMLEntity::UMLFn(f) => uml_graph.add_fn(f),
+            UMLEntity::UMLMacro(m) => uml_graph.add_macro(m),
+            UMLEntity::UMLStatic(s) => uml_graph.add_static(s),
+            UMLEntity::UMLRelation(r) => {
+                // uml_graph.add_relation(r);
+                relations.push(r);
+            },
+            UMLEntity::UMLOuterEntity(oe) => uml_graph.add_outer_entity(oe),
+        }
+    }
+    for c in trait_impls {
+        uml_graph.add_trait_impl(c);
+    }
+    for rel in relations {
+        uml_graph.add_relation(rel);
     }
 }
 
@@ -62,6 +149,19 @@ impl StringParser for AstParser {
 mod tests {
     use super::*;
 
+    fn get_field_relation(from: &str, to: &str, kind: UMLRelationKind, role: &str, from_multiplicity: Option<&str>, to_multiplicity: Option<&str>) -> UMLRelation {
+        let mut rel = UMLRelation::new_with_multiplicities(from, to, kind, from_multiplicity, to_multiplicity);
+        rel.set_role(role);
+        rel
+    }
+
+    fn get_return_relation(from: &str, to: &str, role: &str) -> UMLRelation {
+        // the method returning a type is the role at the returned type's end
+        let mut rel = UMLRelation::new(from, to, UMLRelationKind::UMLAssociationUni);
+        rel.from_role = Some(String::from(role));
+        rel
+    }
+
     #[test]
     fn test_parse_fn() {
         let code: &str = r#"
@@ -72,6 +172,7 @@ mod tests {
         let parsed_graph = AstParser::parse_string(code);
         let mut target_graph: UMLGraph = UMLGraph::new("");
         target_graph.add_fn(UMLFn::new("main", "main()"));
+        target_graph.add_relation(UMLRelation::new("main", "std.println", UMLRelationKind::UMLDependency));
         assert_eq!(parsed_graph, target_graph);
     }
 
@@ -85,7 +186,12 @@ mod tests {
         "#;
         let parsed_graph = AstParser::parse_string(code);
         let mut target_graph: UMLGraph = UMLGraph::new("");
-        target_graph.add_struct(UMLClass::new("Mock", vec![], vec![String::from("mock_fn()")], UMLClassKind::UMLClass));
+        let mut mock = UMLClass::new("Mock", vec![], vec![], UMLClassKind::UMLClass);
+        let mut mock_fn = UMLFn::new("mock_fn", "mock_fn()");
+        mock_fn.visibility = UMLVisibility::Public;
+        mock.add_method(mock_fn);
+        mock.visibility = UMLVisibility::Public;
+        target_graph.add_struct(mock);
         assert_eq!(parsed_graph, target_graph);
     }
 
@@ -120,7 +226,12 @@ mod tests {
         let parsed_graph = AstParser::parse_string(code);
         let mut target_graph: UMLGraph = UMLGraph::new("");
 
-        target_graph.add_struct(UMLClass::new("Mock", vec![], vec![String::from("mock_fn()")], UMLClassKind::UMLClass));
+        let mut mock = UMLClass::new("Mock", vec![], vec![], UMLClassKind::UMLClass);
+        let mut mock_fn = UMLFn::new("mock_fn", "mock_fn()");
+        mock_fn.visibility = UMLVisibility::Public;
+        mock.add_method(mock_fn);
+        mock.visibility = UMLVisibility::Public;
+        target_graph.add_struct(mock);
         target_graph.add_fn(UMLFn::new("f1", "f1(i: usize)"));
         target_graph.add_fn(UMLFn::new("f2", "f2() -> usize"));
         target_graph.add_relation(UMLRelation::new("Mock", "f1", UMLRelationKind::UMLDependency));
@@ -149,8 +260,8 @@ mod tests {
         target_graph.add_struct(UMLClass::new("Amut", vec![String::from(r"b: *mut B")], vec![], UMLClassKind::UMLClass));
         target_graph.add_struct(UMLClass::new("Aconst", vec![String::from(r"b: *const B")], vec![], UMLClassKind::UMLClass));
         target_graph.add_struct(UMLClass::new("B", vec![], vec![], UMLClassKind::UMLClass));
-        target_graph.add_relation(UMLRelation::new("Amut", "B", UMLRelationKind::UMLAggregation));
-        target_graph.add_relation(UMLRelation::new("Aconst", "B", UMLRelationKind::UMLAggregation));
+        target_graph.add_relation(get_field_relation("Amut", "B", UMLRelationKind::UMLAggregation, "b", None, Some("1")));
+        target_graph.add_relation(get_field_relation("Aconst", "B", UMLRelationKind::UMLAggregation, "b", None, Some("1")));
         
         assert_eq!(parsed_graph, target_graph);
     }
@@ -170,7 +281,7 @@ mod tests {
 
         target_graph.add_struct(UMLClass::new("A", vec![String::from(r"b: B")], vec![], UMLClassKind::UMLClass));
         target_graph.add_struct(UMLClass::new("B", vec![], vec![], UMLClassKind::UMLClass));
-        target_graph.add_relation(UMLRelation::new("A", "B", UMLRelationKind::UMLComposition));
+        target_graph.add_relation(get_field_relation("A", "B", UMLRelationKind::UMLComposition, "b", Some("1"), Some("1")));
         
         assert_eq!(parsed_graph, target_graph);
     }
@@ -213,8 +324,16 @@ mod tests {
         let parsed_graph = AstParser::parse_string(code);
         let mut target_graph: UMLGraph = UMLGraph::new("");
 
-        target_graph.add_struct(UMLClass::new("A", vec![String::from(r"a: T")], vec![String::from(r"a(a: T) -> Self")], UMLClassKind::UMLClass));
-        target_graph.add_struct(UMLClass::new("B", vec![], vec![String::from(r"a(&self) -> Option<T>")], UMLClassKind::UMLTrait));
+        let mut a = UMLClass::new("A", vec![String::from(r"a: T")], vec![String::from(r"a(a: T) -> Self")], UMLClassKind::UMLClass);
+        a.set_generic_names(vec![String::from("T: Debug")]);
+        a.set_derive_names(vec![String::from("Debug")]);
+        let mut b_a = UMLFn::from_full_name("a(&self) -> Option<T>");
+        b_a.visibility = UMLVisibility::Public;
+        a.add_trait_methods("B<T>", &mut vec![b_a]);
+        let mut b = UMLClass::new("B", vec![], vec![String::from(r"a(&self) -> Option<T>")], UMLClassKind::UMLTrait);
+        b.set_generic_names(vec![String::from("T: Debug")]);
+        target_graph.add_struct(a);
+        target_graph.add_struct(b);
         target_graph.add_relation(UMLRelation::new("A", "B", UMLRelationKind::UMLRealization));
         
         // relations with invalid end(s) are stored inside the class but cannnot be reached
@@ -262,8 +381,9 @@ mod tests {
         target_graph.add_struct(UMLClass::new("A", vec![], vec![String::from(r"b() -> B")], UMLClassKind::UMLClass));
         target_graph.add_struct(UMLClass::new("Ab", vec![], vec![String::from(r"b() -> B")], UMLClassKind::UMLClass));
         target_graph.add_struct(UMLClass::new("B", vec![], vec![String::from(r"a() -> Ab")], UMLClassKind::UMLClass));
-        target_graph.add_relation(UMLRelation::new("B", "A", UMLRelationKind::UMLAssociationUni));
-        target_graph.add_relation(UMLRelation::new("B", "Ab", UMLRelationKind::UMLAssociationBi));
+        target_graph.add_relation(get_return_relation("B", "A", "b"));
+        target_graph.add_relation(get_return_relation("B", "Ab", "b"));
+        target_graph.add_relation(get_return_relation("Ab", "B", "a"));
         
         assert_eq!(parsed_graph, target_graph);
     }
@@ -286,7 +406,7 @@ mod tests {
         target_graph.add_outer_entity(UMLOuterEntity::new("Hello", "hello"));
         target_graph.add_outer_entity(UMLOuterEntity::new("hello", "hello"));
 
-        target_graph.add_relation(UMLRelation::new("mock", "Hello.new", UMLRelationKind::UMLDependency));
+        target_graph.add_relation(UMLRelation::new("mock", "Hello", UMLRelationKind::UMLDependency));
         target_graph.add_relation(UMLRelation::new("mock", "hello", UMLRelationKind::UMLDependency));
         
         assert_eq!(parsed_graph, target_graph);
@@ -303,16 +423,680 @@ mod tests {
 
         fn mock() -> () {
             Hello::new();
+            utils::hello();
         }
         "#;
         let parsed_graph = AstParser::parse_string(code);
         let mut target_graph: UMLGraph = UMLGraph::new("");
 
         target_graph.add_fn(UMLFn::new("mock", "mock() -> ()"));
-        target_graph.add_struct(UMLClass::new("Hello", vec![], vec![String::from("new()")], UMLClassKind::UMLClass));
-        target_graph.add_relation(UMLRelation::new("mock", "Hello.new", UMLRelationKind::UMLDependency));
+        let mut hello = UMLClass::new("Hello", vec![], vec![], UMLClassKind::UMLClass);
+        let mut hello_new = UMLFn::new("new", "new()");
+        hello_new.visibility = UMLVisibility::Public;
+        hello.add_method(hello_new);
+        target_graph.add_struct(hello);
+        target_graph.add_relation(UMLRelation::new("mock", "Hello", UMLRelationKind::UMLDependency));
+        target_graph.add_relation(UMLRelation::new("mock", "utils.hello", UMLRelationKind::UMLDependency));
         
+        // the constructor call is a dependency on its type, calls into modules keep their path
+        assert_eq!(parsed_graph, target_graph);
+        assert_eq!(parsed_graph.relations().len(), 1);
+    }
+
+    #[test]
+    fn test_enum() {
+        let code: &str = r#"
+        enum Shape {
+            Empty,
+            Circle(f64),
+            Rect { w: Length, h: Length },
+            Group(Vec<B>),
+        }
+
+        impl Shape {
+            fn area(&self) -> f64 { 0.0 }
+        }
+
+        struct Length;
+        struct B;
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        target_graph.add_struct(UMLClass::new_enum(
+            "Shape",
+            vec![String::from("Empty"), String::from("Circle(f64)"), String::from("Rect { w: Length, h: Length }"), String::from("Group(Vec<B>)")],
+            vec![String::from("area(&self) -> f64")]
+        ));
+        target_graph.add_struct(UMLClass::new("Length", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_struct(UMLClass::new("B", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_relation(get_field_relation("Shape", "Length", UMLRelationKind::UMLComposition, "Rect", Some("1"), Some("1")));
+        target_graph.add_relation(get_field_relation("Shape", "B", UMLRelationKind::UMLComposition, "Group", Some("1"), Some("0..*")));
+
+        assert_eq!(parsed_graph.structs(), target_graph.structs());
+        assert_eq!(parsed_graph.relations(), target_graph.relations());
+    }
+
+    #[test]
+    fn test_inline_modules() {
+        let code: &str = r#"
+        fn main() {}
+
+        mod outer {
+            struct A;
+
+            mod inner {
+                fn hello() {}
+            }
+        }
+
+        mod declared;
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+        let mut outer_mod: UMLGraph = UMLGraph::new("outer");
+        let mut inner_mod: UMLGraph = UMLGraph::new("inner");
+
+        inner_mod.add_fn(UMLFn::new("hello", "hello()"));
+        outer_mod.add_struct(UMLClass::new("A", vec![], vec![], UMLClassKind::UMLClass));
+        outer_mod.add_module(inner_mod);
+        target_graph.add_fn(UMLFn::new("main", "main()"));
+        target_graph.add_module(outer_mod);
+
+        assert_eq!(parsed_graph, target_graph);
+    }
+
+    #[test]
+    fn test_trait_items() {
+        let code: &str = r#"
+        trait Walker {
+            type Item: Clone;
+            const DEPTH: usize = 4;
+
+            fn next(&mut self) -> Option<Self::Item>;
+            fn count(&mut self) -> usize {
+                let f = |x: usize| -> usize { x + 1 };
+                f(0)
+            }
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        let mut walker = UMLClass::new(
+            "Walker",
+            vec![String::from("type Item: Clone"), String::from("const DEPTH: usize = 4")],
+            vec![String::from("next(&mut self) -> Option<Self::Item>")],
+            UMLClassKind::UMLTrait
+        );
+        let mut count = UMLFn::from_full_name("count(&mut self) -> usize");
+        count.has_default = true;
+        count.visibility = UMLVisibility::Public;
+        walker.add_method(count);
+        target_graph.add_struct(walker);
+
+        assert_eq!(parsed_graph.structs(), target_graph.structs());
+    }
+
+    #[test]
+    fn test_generalization() {
+        let code: &str = r#"
+        trait Reader: Source + Clone + 'static {}
+        trait Source where Self: Named {}
+        trait Named {}
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        target_graph.add_struct(UMLClass::new("Reader", vec![], vec![], UMLClassKind::UMLTrait));
+        target_graph.add_struct(UMLClass::new("Source", vec![], vec![], UMLClassKind::UMLTrait));
+        target_graph.add_struct(UMLClass::new("Named", vec![], vec![], UMLClassKind::UMLTrait));
+        target_graph.add_relation(UMLRelation::new("Reader", "Source", UMLRelationKind::UMLGeneralization));
+        target_graph.add_relation(UMLRelation::new("Source", "Named", UMLRelationKind::UMLGeneralization));
+
+        assert_eq!(parsed_graph.structs(), target_graph.structs());
+        assert_eq!(parsed_graph.relations(), target_graph.relations());
+    }
+
+    #[test]
+    fn test_generics() {
+        let code: &str = r#"
+        struct Buffer<'a, T: Storage + Clone, const N: usize> where 'a: 'static, T: Named {
+            data: &'a [T; N],
+        }
+
+        trait Storage {}
+        trait Named {}
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        let mut buffer = UMLClass::new("Buffer", vec![String::from("data: &'a [T; N]")], vec![], UMLClassKind::UMLClass);
+        buffer.set_generic_names(vec![String::from("'a: 'static"), String::from("T: Storage + Clone + Named"), String::from("const N: usize")]);
+        target_graph.add_struct(buffer);
+        target_graph.add_struct(UMLClass::new("Storage", vec![], vec![], UMLClassKind::UMLTrait));
+        target_graph.add_struct(UMLClass::new("Named", vec![], vec![], UMLClassKind::UMLTrait));
+        target_graph.add_relation(UMLRelation::new("Buffer", "Storage", UMLRelationKind::UMLDependency));
+        target_graph.add_relation(UMLRelation::new("Buffer", "Named", UMLRelationKind::UMLDependency));
+
+        assert_eq!(parsed_graph.structs(), target_graph.structs());
+        assert_eq!(parsed_graph.relations(), target_graph.relations());
+    }
+
+    #[test]
+    fn test_where_bounds_on_other_types() {
+        let code: &str = r#"
+        struct Wrapper<T> where Vec<T>: Printable, T: Clone {
+            items: Vec<T>,
+        }
+
+        trait Printable {}
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        // only declared params are template params, the other bounds are still depended on
+        let mut wrapper = UMLClass::new("Wrapper", vec![String::from("items: Vec<T>")], vec![], UMLClassKind::UMLClass);
+        wrapper.set_generic_names(vec![String::from("T: Clone")]);
+        target_graph.add_struct(wrapper);
+        target_graph.add_struct(UMLClass::new("Printable", vec![], vec![], UMLClassKind::UMLTrait));
+        target_graph.add_relation(UMLRelation::new("Wrapper", "Printable", UMLRelationKind::UMLDependency));
+
+        assert_eq!(parsed_graph.structs(), target_graph.structs());
+        assert_eq!(parsed_graph.relations(), target_graph.relations());
+    }
+
+    #[test]
+    fn test_visibility() {
+        let code: &str = r#"
+        pub struct A {
+            pub a: usize,
+            pub(crate) b: usize,
+            pub(super) c: usize,
+            d: usize,
+        }
+        impl A {
+            pub fn new() -> Self {}
+            fn get(&self) -> usize {}
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let a = parsed_graph.structs().into_iter().find(|st| st.name == "A").unwrap();
+
+        assert_eq!(a.visibility, UMLVisibility::Public);
+        let field_visibilities: Vec<UMLVisibility> = a.get_fields().iter().map(|f| f.visibility.clone()).collect();
+        assert_eq!(field_visibilities, vec![UMLVisibility::Public, UMLVisibility::Crate, UMLVisibility::Restricted, UMLVisibility::Private]);
+        let method_visibilities: Vec<UMLVisibility> = a.get_methods().iter().map(|m| m.visibility.clone()).collect();
+        assert_eq!(method_visibilities, vec![UMLVisibility::Public, UMLVisibility::Private]);
+    }
+
+    #[test]
+    fn test_derives() {
+        let code: &str = r#"
+        #[derive(Debug, Clone)]
+        #[allow(dead_code)]
+        struct A;
+        #[derive(PartialEq, serde::Serialize)]
+        enum B {}
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        let mut a = UMLClass::new("A", vec![], vec![], UMLClassKind::UMLClass);
+        a.set_derive_names(vec![String::from("Debug"), String::from("Clone")]);
+        target_graph.add_struct(a);
+        let mut b = UMLClass::new_enum("B", vec![], vec![]);
+        b.set_derive_names(vec![String::from("PartialEq"), String::from("serde::Serialize")]);
+        target_graph.add_struct(b);
+
+        assert_eq!(parsed_graph, target_graph);
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let code: &str = r#"
+        //! The crate.
+
+        /// A struct,
+        /// documented.
+        struct A {
+            /// A field.
+            a: usize,
+        }
+        impl A {
+            /** A method. */
+            fn a(&self) {}
+        }
+        /// A module.
+        mod m {
+            //! Inside the module.
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let a = parsed_graph.structs().into_iter().find(|st| st.name == "A").unwrap();
+
+        assert_eq!(parsed_graph.doc, Some(String::from("The crate.")));
+        assert_eq!(a.doc, Some(String::from("A struct,\ndocumented.")));
+        assert_eq!(a.get_fields()[0].doc, Some(String::from("A field.")));
+        assert_eq!(a.get_methods()[0].doc, Some(String::from("A method.")));
+        assert_eq!(parsed_graph.modules["m"].doc, Some(String::from("A module.\nInside the module.")));
+    }
+
+    #[test]
+    fn test_tuple_struct() {
+        let code: &str = r#"
+        struct Unit;
+        struct Wrapper(Unit, *mut Unit);
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        target_graph.add_struct(UMLClass::new("Unit", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_struct(UMLClass::new("Wrapper", vec![String::from("0: Unit"), String::from("1: *mut Unit")], vec![], UMLClassKind::UMLClass));
+        target_graph.add_relation(get_field_relation("Wrapper", "Unit", UMLRelationKind::UMLComposition, "0", Some("1"), Some("1")));
+        target_graph.add_relation(get_field_relation("Wrapper", "Unit", UMLRelationKind::UMLAggregation, "1", None, Some("1")));
+
+        assert_eq!(parsed_graph, target_graph);
+    }
+
+    #[test]
+    fn test_method_call_dependency() {
+        let code: &str = r#"
+        struct Client;
+        struct Store;
+        struct Cache;
+        struct Service(Cache);
+        impl Service {
+            fn run(&self, store: &mut Store) {
+                self.0.clear();
+                store.insert(0);
+            }
+        }
+        fn send() {
+            let c = Box::new(Client::new());
+            c.send();
+            let s: std::rc::Rc<Store> = get();
+            s.insert(0);
+        }
+        fn ping(client: &Client) {
+            client.ping();
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        target_graph.add_struct(UMLClass::new("Client", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_struct(UMLClass::new("Store", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_struct(UMLClass::new("Cache", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_fn(UMLFn::new("send", "send()"));
+        target_graph.add_fn(UMLFn::new("ping", "ping(client: &Client)"));
+        target_graph.add_struct(UMLClass::new("Service", vec![String::from("0: Cache")], vec![String::from("run(&self, store: &mut Store)")], UMLClassKind::UMLClass));
+        target_graph.add_relation(get_field_relation("Service", "Cache", UMLRelationKind::UMLComposition, "0", Some("1"), Some("1")));
+        target_graph.add_relation(UMLRelation::new("Service", "Store", UMLRelationKind::UMLDependency));
+        target_graph.add_relation(UMLRelation::new("send", "Client", UMLRelationKind::UMLDependency));
+        target_graph.add_relation(UMLRelation::new("send", "Store", UMLRelationKind::UMLDependency));
+        target_graph.add_relation(UMLRelation::new("ping", "Client", UMLRelationKind::UMLDependency));
+
+        assert_eq!(parsed_graph.relations(), target_graph.relations());
+    }
+
+    #[test]
+    fn test_fn_qualifiers() {
+        let code: &str = r#"
+        struct A;
+        impl A {
+            const unsafe fn get(&self) {}
+        }
+        async fn run() {}
+        extern "C" fn callback() {}
+        extern fn plain() {}
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let qualifiers: Vec<Vec<UMLQualifier>> = parsed_graph.fns().iter().map(|f| f.qualifiers.clone()).collect();
+
+        assert_eq!(parsed_graph.structs()[0].get_methods()[0].qualifiers, vec![UMLQualifier::Const, UMLQualifier::Unsafe]);
+        assert_eq!(qualifiers, vec![
+            vec![UMLQualifier::Async],
+            vec![UMLQualifier::Extern(Some(String::from("C")))],
+            vec![UMLQualifier::Extern(None)],
+        ]);
+    }
+
+    #[test]
+    fn test_extern_blocks() {
+        let code: &str = r#"
+        extern crate libc as c;
+        extern "C" {
+            fn abs(x: i32) -> i32;
+        }
+        extern "C" {
+            static VERSION: u32;
+        }
+        fn main() {
+            unsafe { abs(-1); }
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut foreign_mod = UMLGraph::new("extern_C");
+        foreign_mod.stereotype = Some(String::from("foreign"));
+        foreign_mod.add_fn(UMLFn::new("abs", "abs(x: i32) -> i32"));
+        let mut foreign_mod_1 = UMLGraph::new("extern_C_1");
+        foreign_mod_1.stereotype = Some(String::from("foreign"));
+        foreign_mod_1.add_static(UMLStatic::new("VERSION", "static VERSION: u32"));
+
+        assert_eq!(parsed_graph.modules["extern_C"], foreign_mod);
+        assert_eq!(parsed_graph.modules["extern_C_1"], foreign_mod_1);
+        assert_eq!(parsed_graph.extern_crates(), vec!["libc as c"]);
+        // calls into the block are resolved to its package
+        assert_eq!(parsed_graph.outer_relations(), vec![UMLRelation::new("main", "self.extern_C.abs", UMLRelationKind::UMLDependency)]);
+    }
+
+    #[test]
+    fn test_blanket_impl() {
+        let code: &str = r#"
+        trait ToPretty {}
+        impl<T: std::fmt::Display> ToPretty for T {}
+        impl<T: ToPretty> ToPretty for &T {}
+        struct W<T>(T);
+        impl<T> ToPretty for W<T> {}
+        struct ToPretty_for_T;
+        impl ToPretty for ToPretty_for_T {}
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut template_class = UMLClass::new("__blanket_ToPretty_for_T", vec![], vec![], UMLClassKind::UMLBlanket(String::from("T")));
+        template_class.set_generic_names(vec![String::from("T: std::fmt::Display")]);
+        let mut ref_template_class = UMLClass::new("__blanket_ToPretty_for_ref_T", vec![], vec![], UMLClassKind::UMLBlanket(String::from("&T")));
+        ref_template_class.set_generic_names(vec![String::from("T: ToPretty")]);
+
+        // generic impls for a concrete type are no blanket ones, and each self type has a template node of its own
+        assert_eq!(parsed_graph.structs().len(), 5);
+        assert_eq!(parsed_graph.structs()[1], &template_class);
+        assert_eq!(parsed_graph.structs()[2], &ref_template_class);
+        assert_eq!(parsed_graph.relations(), vec![
+            &UMLRelation::new("__blanket_ToPretty_for_T", "ToPretty", UMLRelationKind::UMLRealization),
+            &UMLRelation::new("__blanket_ToPretty_for_ref_T", "ToPretty", UMLRelationKind::UMLRealization),
+            &UMLRelation::new("W", "ToPretty", UMLRelationKind::UMLRealization),
+            &UMLRelation::new("ToPretty_for_T", "ToPretty", UMLRelationKind::UMLRealization),
+        ]);
+    }
+
+    #[test]
+    fn test_qualified_trait_impls() {
+        let code: &str = r#"
+        struct X;
+        impl std::fmt::Debug for X {}
+        impl serde::Serialize for X {}
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+
+        // qualified traits are outside of the crate, the relations to them are external ones
+        let targets: Vec<String> = parsed_graph.external_relations().into_iter().map(|r| r.to).collect();
+        assert_eq!(targets, vec![String::from("std.fmt.Debug"), String::from("serde.Serialize")]);
+    }
+
+    #[test]
+    fn test_interface_usage() {
+        let code: &str = r#"
+        trait Storage {}
+        struct Table;
+        impl Table {
+            fn get(&self) -> &dyn Storage { unimplemented!() }
+            fn rows(s: impl Storage<Row = Table> + ?Sized) {}
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut usage = UMLRelation::new("Table", "Storage", UMLRelationKind::UMLInterfaceUsage);
+        usage.bindings = vec![String::from("Row = Table")];
+
+        // not an association from the return type, nor a dependency from the params
+        assert_eq!(parsed_graph.relations(), vec![&usage]);
+    }
+
+    #[test]
+    fn test_self_relations() {
+        let code: &str = r#"
+        struct Node {
+            next: Option<Box<Node>>,
+            prev: Weak<Node>,
+            last: Weak<Node>,
+        }
+        impl Node {
+            fn new() -> Node { Node::default() }
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+        target_graph.add_relation(get_field_relation("Node", "Node", UMLRelationKind::UMLComposition, "next", Some("1"), Some("0..1")));
+        target_graph.add_relation(get_field_relation("Node", "Node", UMLRelationKind::UMLAssociationUni, "prev", None, Some("0..1")));
+        target_graph.add_relation(get_field_relation("Node", "Node", UMLRelationKind::UMLAssociationUni, "last", None, Some("0..1")));
+        target_graph.add_struct(UMLClass::new("Node", vec![], vec![], UMLClassKind::UMLClass));
+
+        // the constructor's return type and body are no relations of their own
+        assert_eq!(parsed_graph.relations(), target_graph.relations());
+
+        let code: &str = r#"
+        struct Span;
+        impl Span {
+            fn new() -> Self { Span }
+            fn join(&self, other: Span) -> Span { other }
+        }
+        struct Token;
+        impl Token {
+            fn absorb(&mut self, other: &Token) {}
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+
+        // methods taking or returning their own type relate it to itself
+        assert_eq!(parsed_graph.relations(), vec![
+            &get_return_relation("Span", "Span", "join"),
+            &UMLRelation::new("Token", "Token", UMLRelationKind::UMLDependency),
+        ]);
+    }
+
+    #[test]
+    fn test_generic_params_are_no_targets() {
+        let code: &str = r#"
+        struct T;
+        struct Item;
+        struct A<T> {
+            a: T,
+            b: Vec<T>,
+            c: Item,
+        }
+        impl<T: Clone> A<T> {
+            fn get<Item>(&self, x: Item) -> Option<T> {
+                let t: T = T::default();
+                t.clone();
+                None
+            }
+            fn put(&self, t: T) {}
+        }
+        fn make<T>() -> T { T::new() }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+
+        // the local `T` and `Item` are shadowed by the generic params everywhere but in `c`
+        assert_eq!(parsed_graph.relations(), vec![&get_field_relation("A", "Item", UMLRelationKind::UMLComposition, "c", Some("1"), Some("1"))]);
+    }
+
+    #[test]
+    fn test_resolve_self() {
+        let code: &str = r#"
+        struct Node {
+            next: Option<Box<Self>>,
+        }
+        enum List {
+            Cons(i32, Box<Self>),
+            Nil,
+        }
+        struct Store;
+        impl Store {
+            fn get(&self) -> Self::Out { unimplemented!() }
+        }
+        struct Client;
+        impl Client {
+            fn send(&self) {
+                let s = Self::new();
+                s.flush();
+            }
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+
+        // `Self` in a type's own impl only points back at the type itself
+        assert_eq!(parsed_graph.relations(), vec![
+            &get_field_relation("Node", "Node", UMLRelationKind::UMLComposition, "next", Some("1"), Some("0..1")),
+            &get_field_relation("List", "List", UMLRelationKind::UMLComposition, "Cons", Some("1"), Some("1")),
+        ]);
+    }
+
+    #[test]
+    fn test_receivers() {
+        let code: &str = r#"
+        struct A;
+        impl A {
+            fn new() -> Self { A }
+            fn get(&'a self) {}
+            fn set(&mut self, a: A) {}
+            fn into_inner(mut self) {}
+            fn boxed(self: Box<Self>) {}
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+
+        // associated fns have no self param
+        let receivers: Vec<Option<UMLReceiver>> = parsed_graph.structs()[0].get_methods().iter().map(|m| m.receiver.clone()).collect();
+        assert_eq!(receivers, vec![
+            None,
+            Some(UMLReceiver::Ref),
+            Some(UMLReceiver::RefMut),
+            Some(UMLReceiver::Value),
+            Some(UMLReceiver::Typed(String::from("Box<Self>")))
+        ]);
+    }
+
+    #[test]
+    fn test_trait_impl_methods() {
+        let code: &str = r#"
+        impl Display for Foo {
+            fn fmt(&self, f: &mut Formatter) -> Result { Ok(()) }
+        }
+        struct Foo;
+        impl Debug for Foo {
+            fn fmt(&self, f: &mut Formatter) -> Result { Ok(()) }
+        }
+        impl From<Foo> for String {
+            fn from(f: Foo) -> Self { String::new() }
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+
+        // grouped by trait on the implementing class, and a foreign type gets no node
+        let mut foo = UMLClass::new("Foo", vec![], vec![], UMLClassKind::UMLClass);
+        let mut fmt = UMLFn::from_full_name("fmt(&self, f: &mut Formatter) -> Result");
+        fmt.visibility = UMLVisibility::Public;
+        foo.add_trait_methods("Display", &mut vec![fmt.clone()]);
+        foo.add_trait_methods("Debug", &mut vec![fmt]);
+        assert_eq!(parsed_graph.structs(), vec![&foo]);
+    }
+
+    #[test]
+    fn test_macros() {
+        let code: &str = r#"
+        #[macro_export]
+        macro_rules! vec_of {
+            ($($x:expr),*) => { vec![$($x),*] };
+        }
+        fn main() {
+            let v = vec_of![1, 2];
+            println!("{:?}", vec![0]);
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        let mut vec_of = UMLMacro::new("vec_of", vec![String::from("($($x:expr),*)")]);
+        vec_of.visibility = UMLVisibility::Public;
+        target_graph.add_macro(vec_of);
+        target_graph.add_fn(UMLFn::new("main", "main()"));
+        target_graph.add_relation(UMLRelation::new("main", "vec_of", UMLRelationKind::UMLDependency));
+        target_graph.add_relation(UMLRelation::new("main", "std.println", UMLRelationKind::UMLDependency));
+        target_graph.add_relation(UMLRelation::new("main", "std.vec", UMLRelationKind::UMLDependency));
+
         assert_eq!(parsed_graph, target_graph);
+        // std macros are kept apart from the crate's own
+        assert_eq!(parsed_graph.relations().len(), 1);
+        assert_eq!(parsed_graph.std_macro_relations().len(), 2);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_smart_pointers() {
+        let code: &str = r#"
+        struct A;
+        struct B;
+        struct C;
+        struct D;
+        struct Rc;
+        struct Holder {
+            a: Option<Box<A>>,
+            b: std::rc::Rc<std::cell::RefCell<B>>,
+            c: std::sync::Weak<C>,
+            d: &'static [(D, A)],
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let mut target_graph: UMLGraph = UMLGraph::new("");
+
+        target_graph.add_struct(UMLClass::new("A", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_struct(UMLClass::new("B", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_struct(UMLClass::new("C", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_struct(UMLClass::new("D", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_struct(UMLClass::new("Rc", vec![], vec![], UMLClassKind::UMLClass));
+        target_graph.add_relation(get_field_relation("Holder", "A", UMLRelationKind::UMLComposition, "a", Some("1"), Some("0..1")));
+        target_graph.add_relation(get_field_relation("Holder", "B", UMLRelationKind::UMLAggregation, "b", None, Some("1")));
+        target_graph.add_relation(get_field_relation("Holder", "C", UMLRelationKind::UMLAssociationUni, "c", None, Some("0..1")));
+        target_graph.add_relation(get_field_relation("Holder", "D", UMLRelationKind::UMLAggregation, "d", None, Some("0..*")));
+        target_graph.add_relation(get_field_relation("Holder", "A", UMLRelationKind::UMLAggregation, "d", None, Some("0..*")));
+        target_graph.add_struct(UMLClass::new("Holder", vec![], vec![], UMLClassKind::UMLClass));
+
+        // wrappers are looked through, even the ones with the name of a local type, and each field keeps its own edge
+        assert_eq!(parsed_graph.relations(), target_graph.relations());
+    }
+
+    #[test]
+    fn test_multiplicities() {
+        let code: &str = r#"
+        struct Node;
+        struct Leaf;
+        struct Edge;
+        struct Error;
+        struct Tree {
+            children: Vec<Node>,
+            parent: Option<Node>,
+            leaves: [Leaf; 4],
+            edges: Option<std::collections::HashMap<String, Edge>>,
+            root: Result<Leaf, Error>,
+        }
+        enum Link {
+            Pair(Option<Node>, Vec<Node>),
+        }
+        "#;
+        let parsed_graph = AstParser::parse_string(code);
+        let relations = parsed_graph.relations();
+        let get_multiplicity = |role: &str| {
+            relations.iter().find(|r| r.to_role.as_deref() == Some(role)).and_then(|r| r.to_multiplicity.clone())
+        };
+
+        assert_eq!(get_multiplicity("children"), Some(String::from("0..*")));
+        assert_eq!(get_multiplicity("parent"), Some(String::from("0..1")));
+        assert_eq!(get_multiplicity("leaves"), Some(String::from("4")));
+        assert_eq!(get_multiplicity("edges"), Some(String::from("0..*")));
+        assert_eq!(get_multiplicity("root"), Some(String::from("1")));
+        assert!(relations.iter().all(|r| r.from_multiplicity == Some(String::from("1"))));
+        // one relation per field, even with the same role
+        assert_eq!(relations.iter().filter(|r| r.from == "Tree" && r.to == "Node").count(), 2);
+        assert_eq!(relations.iter().filter(|r| r.from == "Link" && r.to == "Node").count(), 2);
+    }
+
+}
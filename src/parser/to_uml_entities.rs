@@ -1,10 +1,13 @@
 use crate::uml_entity::UMLEntity;
 
-mod utils;
+pub(super) mod utils;
 mod ast_struct;
+mod ast_enum;
 mod ast_trait;
 mod ast_impl;
 mod ast_fn;
+mod ast_macro;
+mod ast_static;
 mod ast_use;
 
 pub trait HasUMLEntity {
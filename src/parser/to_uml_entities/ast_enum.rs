@@ -0,0 +1,48 @@
+use ra_ap_syntax::{ast::{self, AstNode, HasName, HasVisibility}, match_ast};
+use super::HasUMLEntity;
+use crate::uml_entity::*;
+use super::utils::{get_field_relations, get_visibility, get_derive_names, get_doc_text, get_variant_full_name, get_generic_param_names, get_generic_bound_names};
+
+impl HasUMLEntity for ast::Enum {
+    fn get_uml_entities(&self) -> Vec<UMLEntity> {
+        let mut results = vec![];
+        let enum_name = self.name().unwrap().text().to_string();
+        let mut variant_names = vec![];
+
+        if let Some(vl) = self.variant_list() {
+            for variant in vl.variants() {
+                // get variants for UMLClass
+                variant_names.push(get_variant_full_name(&variant));
+
+                // get Aggregation and Composition Relations from variants' payloads, the variant being the role
+                let variant_name = variant.name().unwrap().text().to_string();
+                for node in variant.syntax().descendants() {
+                    match_ast! {
+                        match node {
+                            ast::RecordField(rf) => {
+                                results.append(&mut get_field_relations(&enum_name, &variant_name, rf.ty()));
+                            },
+                            ast::TupleField(tf) => {
+                                results.append(&mut get_field_relations(&enum_name, &variant_name, tf.ty()));
+                            },
+                            _ => ()
+                        }
+                    }
+                }
+            }
+        }
+
+        // get Dependency Relations from traits bounding the generic params
+        results.extend(
+            get_generic_bound_names(self).iter().map(|b| UMLEntity::UMLRelation(UMLRelation::new(&enum_name, b, UMLRelationKind::UMLDependency)))
+        );
+
+        let mut enum_class = UMLClass::new_enum(&enum_name, variant_names, vec![]);
+        enum_class.set_generic_names(get_generic_param_names(self));
+        enum_class.visibility = get_visibility(self.visibility());
+        enum_class.set_derive_names(get_derive_names(self));
+        enum_class.doc = get_doc_text(self);
+        results.push(UMLEntity::UMLClass(enum_class));
+        results
+    }
+}
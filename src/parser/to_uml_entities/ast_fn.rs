@@ -1,7 +1,7 @@
-use ra_ap_syntax::{ast::{self, AstNode, HasName}, match_ast};
+use ra_ap_syntax::{ast::{self, AstNode, HasName, HasVisibility}, match_ast};
 use super::HasUMLEntity;
 use crate::uml_entity::*;
-use super::utils::{get_fn_full_name, get_call_expr_fn_names, replace_coloncolon_path};
+use super::utils::{get_fn_full_name, get_fn_qualifiers, get_interface_usages, get_visibility, get_doc_text, get_call_expr_fn_names, get_method_call_type_names, get_macro_call_names, replace_coloncolon_path};
 
 impl HasUMLEntity for ast::Fn {
     fn get_uml_entities(&self) -> Vec<UMLEntity> {
@@ -14,8 +14,9 @@ impl HasUMLEntity for ast::Fn {
             match_ast! {
                 match node {
                     ast::CallExpr(it) => {
-                        let call_name = get_call_expr_fn_names(it);
-                        results.push(UMLEntity::UMLRelation(UMLRelation::new(&f_name, &replace_coloncolon_path(&call_name), UMLRelationKind::UMLDependency)))
+                        if let Some(call_name) = get_call_expr_fn_names(it) {
+                            results.push(UMLEntity::UMLRelation(UMLRelation::new(&f_name, &replace_coloncolon_path(&call_name), UMLRelationKind::UMLDependency)))
+                        }
                     },
                     _ => {
                         // println!("{:?}", node);
@@ -24,7 +25,27 @@ impl HasUMLEntity for ast::Fn {
                 }
             }
         }
-        results.push(UMLEntity::UMLFn(UMLFn::new(&f_name, &full_name)));
+        // get Dependency Relations to the types of method call receivers
+        results.extend(
+            get_method_call_type_names(self, &[]).iter().map(|t| UMLEntity::UMLRelation(UMLRelation::new(&f_name, t, UMLRelationKind::UMLDependency)))
+        );
+        // get Interface Usage Relations to the traits behind `dyn` and `impl` in the signature
+        results.extend(
+            get_interface_usages(self).into_iter().map(|(t, bindings)| {
+                let mut rel = UMLRelation::new(&f_name, &t, UMLRelationKind::UMLInterfaceUsage);
+                rel.bindings = bindings;
+                UMLEntity::UMLRelation(rel)
+            })
+        );
+        // get Dependency Relations to the invoked macros
+        results.extend(
+            get_macro_call_names(self.syntax()).iter().map(|m| UMLEntity::UMLRelation(UMLRelation::new(&f_name, m, UMLRelationKind::UMLDependency)))
+        );
+        let mut uml_fn = UMLFn::new(&f_name, &full_name);
+        uml_fn.visibility = get_visibility(self.visibility());
+        uml_fn.doc = get_doc_text(self);
+        uml_fn.qualifiers = get_fn_qualifiers(self);
+        results.push(UMLEntity::UMLFn(uml_fn));
         results
     }
 }
\ No newline at end of file
@@ -1,66 +1,150 @@
-use ra_ap_syntax::{ast::{self, AstNode}, match_ast};
+use ra_ap_syntax::{ast::{self, AstNode, HasName, HasVisibility}, match_ast};
 use super::HasUMLEntity;
 use crate::uml_entity::*;
-use super::utils::{get_paths_str_from_ast_node, strip_trait_bound, get_fn_full_name};
+use super::utils::{get_paths_str_from_ast_node, get_signature_paths_str, strip_trait_bound, get_blanket_param_name, get_blanket_class_name, get_generic_param_names, get_fn_full_name, get_fn_receiver, get_fn_qualifiers, get_visibility, get_doc_text, get_method_call_type_names, get_struct_field_types, get_macro_call_names, get_interface_usages, replace_coloncolon_path};
 
 impl HasUMLEntity for ast::Impl {
     fn get_uml_entities(&self) -> Vec<UMLEntity> {
         let mut results = vec![];
 
-        // get struct name
-        let mut impl_fn_names = vec![];
-        let struct_name: String = strip_trait_bound(&self.self_ty().unwrap().to_string());
+        // get struct name, blanket impls are for a template node named after the trait and the self type
+        let blanket_param = get_blanket_param_name(self);
+        let struct_name: String = match (&blanket_param, self.trait_()) {
+            (Some(_), Some(tt)) => get_blanket_class_name(&strip_trait_bound(&tt.to_string()), &self.self_ty().unwrap().to_string()),
+            _ => strip_trait_bound(&self.self_ty().unwrap().to_string())
+        };
 
         let mut dep_list: Vec<String> = vec![];
-        let mut asct_list: Vec<String> = vec![];
+        let mut asct_list: Vec<(String, String)> = vec![];
         for node in self.syntax().descendants() {
             match_ast! {
                 match node {
-                    // get impl functions' names
-                    ast::Fn(f) => {
-                        impl_fn_names.push(get_fn_full_name(&f));
-                    },
                     // get Dependency and Association Relations
                     ast::ParamList(pl) => {
-                        dep_list.append(&mut get_paths_str_from_ast_node(pl));
+                        // the receiver is none of the type's relations, even if typed like `self: Box<Self>`
+                        pl.params().for_each(|p| dep_list.append(&mut get_signature_paths_str(p)));
                     },
                     ast::BlockExpr(ex) => {
-                        dep_list.append(&mut get_paths_str_from_ast_node(ex));
+                        // building the type or calling its associated fns in its own bodies is no relation to itself
+                        let mut paths = get_paths_str_from_ast_node(ex);
+                        paths.retain(|p| p != &struct_name);
+                        dep_list.append(&mut paths);
                     },
                     ast::RetType(rt) => {
-                        asct_list.append(&mut get_paths_str_from_ast_node(rt));
+                        // the returning method is the role of the association, at the returned type's end
+                        let f = rt.syntax().parent().and_then(ast::Fn::cast);
+                        let fn_name = f.as_ref().and_then(|f| f.name()).map(|n| n.text().to_string()).unwrap_or_default();
+                        let mut paths = get_signature_paths_str(rt);
+                        if f.map(|f| is_constructor(&f, &struct_name)).unwrap_or(false) {
+                            paths.retain(|p| p != &struct_name);
+                        }
+                        asct_list.extend(paths.into_iter().map(|p| (p, fn_name.clone())));
                     },
                     _ => ()
                 }
             }
         }
 
+        // methods called on fields, params and local bindings depend on the types owning them, but the type's own ones
+        let field_types = get_struct_field_types(self, &struct_name);
+        let mut usage_list: Vec<(String, Vec<String>)> = vec![];
+        if let Some(item_list) = self.assoc_item_list() {
+            for assoc_item in item_list.assoc_items() {
+                if let ast::AssocItem::Fn(f) = assoc_item {
+                    dep_list.extend(get_method_call_type_names(&f, &field_types).into_iter().filter(|t| t != &struct_name));
+                    dep_list.append(&mut get_macro_call_names(f.syntax()));
+                    usage_list.append(&mut get_interface_usages(&f));
+                }
+            }
+        }
+
+        // traits used behind `dyn` and `impl` in the signature are no dependencies of the bodies either
+        let is_used_interface = |p: &String| usage_list.iter().any(|(t, _)| t == &replace_coloncolon_path(&strip_trait_bound(p)));
+        dep_list.retain(|p| !is_used_interface(p));
+        results.extend(
+            usage_list.iter().map(|(t, bindings)| {
+                let mut rel = UMLRelation::new(&struct_name, t, UMLRelationKind::UMLInterfaceUsage);
+                rel.bindings = bindings.clone();
+                UMLEntity::UMLRelation(rel)
+            })
+        );
+
         // first add Association Relation, then add dependency relation if the name not occured in assocaitions
         results.extend(
-            asct_list.iter().map(|p| UMLEntity::UMLRelation(UMLRelation::new(&p, &struct_name, UMLRelationKind::UMLAssociationUni)))
+            asct_list.iter().map(|(p, role)| {
+                let mut rel = UMLRelation::new(p, &struct_name, UMLRelationKind::UMLAssociationUni);
+                rel.from_role = Some(role.clone());
+                UMLEntity::UMLRelation(rel)
+            })
         );
-        let mut dep_set: Vec<&String> = dep_list.iter().filter(|p| !asct_list.contains(p)).collect();
+        let mut dep_set: Vec<&String> = dep_list.iter().filter(|p| !asct_list.iter().any(|(a, _)| &a == p)).collect();
         dep_set.sort();
         dep_set.dedup();
         results.extend(
-            dep_set.iter().map(|p| UMLEntity::UMLRelation(UMLRelation::new(&struct_name, &p, UMLRelationKind::UMLDependency)))
+            dep_set.iter().map(|p| UMLEntity::UMLRelation(UMLRelation::new(&struct_name, p, UMLRelationKind::UMLDependency)))
         );
 
 
+        // get impl functions, but not the ones nested inside their bodies
+        let mut methods: Vec<UMLFn> = vec![];
+        if let Some(item_list) = self.assoc_item_list() {
+            for assoc_item in item_list.assoc_items() {
+                if let ast::AssocItem::Fn(f) = assoc_item {
+                    let mut method = UMLFn::new(f.name().unwrap().text().as_str(), &get_fn_full_name(&f));
+                    method.receiver = get_fn_receiver(&f);
+                    method.visibility = get_visibility(f.visibility());
+                    method.doc = get_doc_text(&f);
+                    method.qualifiers = get_fn_qualifiers(&f);
+                    methods.push(method);
+                }
+            }
+        }
+
         // get trait if there is any
         match self.trait_() {
             Some(tt) => {
+                // full paths like `std::fmt::Debug` are dotted as everywhere else, so they can be resolved
+                let trait_name = replace_coloncolon_path(&strip_trait_bound(&tt.to_string()));
                 results.push(
-                    UMLEntity::UMLRelation(UMLRelation::new(&struct_name, &strip_trait_bound(&tt.to_string()), UMLRelationKind::UMLRealization))
+                    UMLEntity::UMLRelation(UMLRelation::new(&struct_name, &trait_name, UMLRelationKind::UMLRealization))
                 );
-                // println!("trait: {}", tt.to_string());
+                // trait impl methods are as public as the trait, and grouped by it on the implementing class
+                methods.iter_mut().for_each(|m| m.visibility = UMLVisibility::Public);
+                let mut impl_class = match &blanket_param {
+                    Some(param) => {
+                        // only the blanket param with its bounds, like "T: Display"
+                        let mut template_class = UMLClass::new(&struct_name, vec![], vec![], UMLClassKind::UMLBlanket(self.self_ty().unwrap().to_string()));
+                        template_class.set_generic_names(
+                            get_generic_param_names(self)
+                                .into_iter()
+                                .filter(|g| g == param || g.starts_with(&format!("{}:", param)))
+                                .collect()
+                        );
+                        template_class
+                    },
+                    None => UMLClass::new(&struct_name, vec![], vec![], UMLClassKind::UMLClass)
+                };
+                if !methods.is_empty() {
+                    impl_class.add_trait_methods(&tt.to_string(), &mut methods);
+                }
+                if blanket_param.is_some() || impl_class.is_trait_impl_only() {
+                    results.push(UMLEntity::UMLClass(impl_class));
+                }
             },
             None => {
-                results.push(UMLEntity::UMLClass(UMLClass::new(&struct_name, vec![], impl_fn_names, UMLClassKind::UMLClass)));
+                let mut impl_class = UMLClass::new(&struct_name, vec![], vec![], UMLClassKind::UMLClass);
+                methods.into_iter().for_each(|m| impl_class.add_method(m));
+                results.push(UMLEntity::UMLClass(impl_class));
             }
         }
-        
+
 
         results
     }
-}
\ No newline at end of file
+}
+
+fn is_constructor(f: &ast::Fn, struct_name: &str) -> bool {
+    // constructors return the type they belong to, which is no relation of the type to itself
+    get_fn_receiver(f).is_none() &&
+        f.ret_type().map(|rt| get_signature_paths_str(rt).iter().any(|p| p == struct_name)).unwrap_or(false)
+}
@@ -0,0 +1,27 @@
+use ra_ap_syntax::{ast::{self, AstNode, HasName, HasAttrs}, SyntaxKind};
+use super::HasUMLEntity;
+use crate::uml_entity::*;
+use super::utils::get_doc_text;
+
+impl HasUMLEntity for ast::MacroRules {
+    fn get_uml_entities(&self) -> Vec<UMLEntity> {
+        // rules are `(matcher) => { transcriber };`, only the matchers are kept
+        let rules: Vec<String> = match self.token_tree() {
+            Some(tt) => tt.syntax()
+                .children()
+                .filter(|n| n.kind() == SyntaxKind::TOKEN_TREE)
+                .step_by(2)
+                .map(|n| n.to_string().split_whitespace().collect::<Vec<&str>>().join(" "))
+                .collect(),
+            None => vec![]
+        };
+
+        let mut uml_macro = UMLMacro::new(self.name().unwrap().text().as_str(), rules);
+        // exported macros are the public ones, they have no other visibility
+        if self.attrs().any(|attr| attr.as_simple_atom().map(|a| a == "macro_export").unwrap_or(false)) {
+            uml_macro.visibility = UMLVisibility::Public;
+        }
+        uml_macro.doc = get_doc_text(self);
+        vec![UMLEntity::UMLMacro(uml_macro)]
+    }
+}
@@ -0,0 +1,18 @@
+use ra_ap_syntax::ast::{self, HasName, HasVisibility};
+use super::HasUMLEntity;
+use crate::uml_entity::*;
+use super::utils::{get_visibility, get_doc_text};
+
+impl HasUMLEntity for ast::Static {
+    fn get_uml_entities(&self) -> Vec<UMLEntity> {
+        let static_name = self.name().unwrap().text().to_string();
+        let full_name = match self.mut_token() {
+            Some(_) => format!("static mut {}: {}", static_name, self.ty().unwrap()),
+            None => format!("static {}: {}", static_name, self.ty().unwrap())
+        };
+        let mut uml_static = UMLStatic::new(&static_name, &full_name);
+        uml_static.visibility = get_visibility(self.visibility());
+        uml_static.doc = get_doc_text(self);
+        vec![UMLEntity::UMLStatic(uml_static)]
+    }
+}
@@ -1,42 +1,54 @@
-use ra_ap_syntax::{ast::{self, AstNode, HasName}, match_ast};
+use ra_ap_syntax::ast::{self, HasName, HasVisibility};
 use super::HasUMLEntity;
 use crate::uml_entity::*;
-use super::utils::get_paths_str_from_ast_node;
+use super::utils::{get_field_relations, get_visibility, get_derive_names, get_doc_text, get_generic_param_names, get_generic_bound_names};
 
 impl HasUMLEntity for ast::Struct {
     fn get_uml_entities(&self) -> Vec<UMLEntity> {
         let mut results = vec![];
-        let mut record_fields = vec![];
-        for node in self.syntax().descendants() {
-            match_ast! {
-                match node {
-                    ast::RecordField(rf) => {
-                        // get fields for UMLClass
-                        record_fields.push(rf.to_string());
+        let struct_name = self.name().unwrap().text().to_string();
+        let mut fields = vec![];
+        match self.field_list() {
+            Some(ast::FieldList::RecordFieldList(rfl)) => {
+                for rf in rfl.fields() {
+                    // get fields for UMLClass
+                    let rf_name = rf.name().unwrap().text().to_string();
+                    let mut field = UMLField::new(&rf_name, &format!("{}: {}", rf_name, rf.ty().unwrap()));
+                    field.visibility = get_visibility(rf.visibility());
+                    field.doc = get_doc_text(&rf);
+                    fields.push(field);
 
-                        // get Aggregation and Composition Relations
-                        let rf_str = rf.to_string();
-                        if rf_str.contains(r"*mut") || rf_str.contains(r"*const") {
-                            get_paths_str_from_ast_node(rf)
-                                .iter()
-                                .for_each(|p| results.push(
-                                    UMLEntity::UMLRelation(UMLRelation::new(&self.name().unwrap().text().to_string(), &p, UMLRelationKind::UMLAggregation)))
-                                )
-                        } else if !rf_str.contains(r"*mut") && !rf_str.contains(r"*const") {
-                            get_paths_str_from_ast_node(rf)
-                                .iter()
-                                .for_each(|p| results.push(
-                                    UMLEntity::UMLRelation(UMLRelation::new(&self.name().unwrap().text().to_string(), &p, UMLRelationKind::UMLComposition)))
-                                )
-                        }
-                    },
-                    _ => ()
+                    // get Aggregation and Composition Relations
+                    results.append(&mut get_field_relations(&struct_name, &rf_name, rf.ty()));
                 }
-            }
-            // println!("{:?}", node);
-            // println!("{}", node);
-        };
-        results.push(UMLEntity::UMLClass(UMLClass::new(&self.name().unwrap().text().to_string(), record_fields, vec![], UMLClassKind::UMLClass)));
+            },
+            Some(ast::FieldList::TupleFieldList(tfl)) => {
+                for (idx, tf) in tfl.fields().enumerate() {
+                    // tuple fields are named by their position
+                    let mut field = UMLField::new(&idx.to_string(), &format!("{}: {}", idx, tf.ty().unwrap()));
+                    field.visibility = get_visibility(tf.visibility());
+                    field.doc = get_doc_text(&tf);
+                    fields.push(field);
+
+                    results.append(&mut get_field_relations(&struct_name, &idx.to_string(), tf.ty()));
+                }
+            },
+            // unit struct
+            None => ()
+        }
+
+        // get Dependency Relations from traits bounding the generic params
+        results.extend(
+            get_generic_bound_names(self).iter().map(|b| UMLEntity::UMLRelation(UMLRelation::new(&struct_name, b, UMLRelationKind::UMLDependency)))
+        );
+
+        let mut struct_class = UMLClass::new(&struct_name, vec![], vec![], UMLClassKind::UMLClass);
+        fields.into_iter().for_each(|f| struct_class.add_field(f));
+        struct_class.visibility = get_visibility(self.visibility());
+        struct_class.set_derive_names(get_derive_names(self));
+        struct_class.doc = get_doc_text(self);
+        struct_class.set_generic_names(get_generic_param_names(self));
+        results.push(UMLEntity::UMLClass(struct_class));
         results
     }
 }
\ No newline at end of file
@@ -1,13 +1,65 @@
-use ra_ap_syntax::{ast::{self, AstNode, HasName}, match_ast};
+use ra_ap_syntax::{ast::{self, AstNode, HasName, HasTypeBounds, HasGenericParams, HasVisibility}, match_ast};
 use super::HasUMLEntity;
 use crate::uml_entity::*;
-use super::utils::get_paths_str_from_ast_node;
+use super::utils::{get_paths_str_from_ast_node, get_visibility, get_doc_text, get_fn_full_name, get_fn_receiver, get_fn_qualifiers, get_trait_bound_names, get_generic_param_names, get_generic_bound_names, get_assoc_type_full_name, get_assoc_const_full_name};
 
 impl HasUMLEntity for ast::Trait {
     fn get_uml_entities(&self) -> Vec<UMLEntity> {
         let mut results = vec![];
-        // add UMLClass
-        results.push(UMLEntity::UMLClass(UMLClass::new(&self.name().unwrap().text().to_string(), vec![], vec![], UMLClassKind::UMLTrait)));
+        // add UMLClass with associated types and consts as fields, and required and provided methods
+        // all of them are as public as the trait itself
+        let mut trait_class = UMLClass::new(self.name().unwrap().text().as_str(), vec![], vec![], UMLClassKind::UMLTrait);
+        if let Some(item_list) = self.assoc_item_list() {
+            for assoc_item in item_list.assoc_items() {
+                match assoc_item {
+                    ast::AssocItem::Fn(f) => {
+                        let mut method = UMLFn::new(f.name().unwrap().text().as_str(), &get_fn_full_name(&f));
+                        method.receiver = get_fn_receiver(&f);
+                        method.has_default = f.body().is_some();
+                        method.qualifiers = get_fn_qualifiers(&f);
+                        method.visibility = UMLVisibility::Public;
+                        method.doc = get_doc_text(&f);
+                        trait_class.add_method(method);
+                    },
+                    ast::AssocItem::TypeAlias(ta) => {
+                        let mut field = UMLField::new(ta.name().unwrap().text().as_str(), &get_assoc_type_full_name(&ta));
+                        field.visibility = UMLVisibility::Public;
+                        field.doc = get_doc_text(&ta);
+                        trait_class.add_field(field);
+                    },
+                    ast::AssocItem::Const(c) => {
+                        let mut field = UMLField::new(c.name().unwrap().text().as_str(), &get_assoc_const_full_name(&c));
+                        field.visibility = UMLVisibility::Public;
+                        field.doc = get_doc_text(&c);
+                        trait_class.add_field(field);
+                    },
+                    _ => ()
+                }
+            }
+        }
+        trait_class.set_generic_names(get_generic_param_names(self));
+        trait_class.visibility = get_visibility(self.visibility());
+        trait_class.doc = get_doc_text(self);
+        results.push(UMLEntity::UMLClass(trait_class));
+
+        // get Generalization Relations from supertraits, declared as bounds or in `where Self: ...`
+        let mut supertrait_names = vec![];
+        if let Some(tbl) = self.type_bound_list() {
+            supertrait_names.append(&mut get_trait_bound_names(&tbl));
+        }
+        if let Some(wc) = self.where_clause() {
+            wc.predicates()
+                .filter(|wp| wp.ty().map(|ty| ty.to_string()) == Some(String::from("Self")))
+                .filter_map(|wp| wp.type_bound_list())
+                .for_each(|tbl| supertrait_names.append(&mut get_trait_bound_names(&tbl)));
+        }
+        // get Dependency Relations from traits bounding the generic params
+        results.extend(
+            get_generic_bound_names(self).iter().map(|b| UMLEntity::UMLRelation(UMLRelation::new(self.name().unwrap().text().as_str(), b, UMLRelationKind::UMLDependency)))
+        );
+        results.extend(
+            supertrait_names.iter().map(|st| UMLEntity::UMLRelation(UMLRelation::new(self.name().unwrap().text().as_str(), st, UMLRelationKind::UMLGeneralization)))
+        );
 
         for node in self.syntax().descendants() {
             match_ast! {
@@ -19,13 +71,13 @@ impl HasUMLEntity for ast::Trait {
                             get_paths_str_from_ast_node(rf)
                                 .iter()
                                 .for_each(|p| results.push(
-                                    UMLEntity::UMLRelation(UMLRelation::new(&self.name().unwrap().text().to_string(), &p, UMLRelationKind::UMLAggregation)))
+                                    UMLEntity::UMLRelation(UMLRelation::new(self.name().unwrap().text().as_str(), p, UMLRelationKind::UMLAggregation)))
                                 )
                         } else if !rf_str.contains(r"*mut") && !rf_str.contains(r"*const") {
                             get_paths_str_from_ast_node(rf)
                                 .iter()
                                 .for_each(|p| results.push(
-                                    UMLEntity::UMLRelation(UMLRelation::new(&self.name().unwrap().text().to_string(), &p, UMLRelationKind::UMLComposition)))
+                                    UMLEntity::UMLRelation(UMLRelation::new(self.name().unwrap().text().as_str(), p, UMLRelationKind::UMLComposition)))
                                 )
                         }
                     },
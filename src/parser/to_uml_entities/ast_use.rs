@@ -14,13 +14,16 @@ impl HasUMLEntity for ast::Use {
     }
 }
 
-fn walk_use_tree(ut: ast::UseTree, outer_entities: &mut Vec<UMLOuterEntity>, path_name: Option<&str>) -> () {
+fn walk_use_tree(ut: ast::UseTree, outer_entities: &mut Vec<UMLOuterEntity>, path_name: Option<&str>) {
     // recursivelly add sub use tree's entities
-    println!("use tree: {}", ut.to_string());
+    if ut.star_token().is_some() {
+        // glob imports cannot be resolved to entities
+        return
+    }
     let ut_path = ut.path().unwrap().to_string();
 
     let current_path_name = match path_name {
-        Some(pn) => vec![pn, ".", &ut_path].concat(),
+        Some(pn) => [pn, ".", &ut_path].concat(),
         None => ut_path.clone()
     };
 
@@ -38,7 +41,7 @@ fn walk_use_tree(ut: ast::UseTree, outer_entities: &mut Vec<UMLOuterEntity>, pat
                 None => {
                     let ut_path_string = replace_coloncolon_path(&ut_path);
                     let mut ut_dot_path: Vec<&str> = ut_path_string.split(".").collect();
-                    let name = ut_dot_path[ut_dot_path.len()-1].clone();
+                    let name = ut_dot_path[ut_dot_path.len()-1];
                     ut_dot_path.truncate(ut_dot_path.len().saturating_sub(1));
                     let mod_name = ut_dot_path.join(".");
                     outer_entities.push(UMLOuterEntity::new(name, &mod_name))
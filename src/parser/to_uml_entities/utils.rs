@@ -1,4 +1,5 @@
-use ra_ap_syntax::{ast::{self, AstNode, HasName}, match_ast};
+use ra_ap_syntax::{ast::{self, AstNode, HasName, HasTypeBounds, HasGenericParams, HasAttrs, HasDocComments, HasArgList, DocCommentIter, VisibilityKind}, match_ast, SyntaxNode, SyntaxKind};
+use crate::uml_entity::{UMLEntity, UMLRelation, UMLRelationKind, UMLVisibility, UMLQualifier, UMLReceiver, UMLMacro};
 
 pub fn get_paths_str_from_ast_node(node: impl ast::AstNode) -> Vec<String> {
     // get raw relation string
@@ -7,7 +8,11 @@ pub fn get_paths_str_from_ast_node(node: impl ast::AstNode) -> Vec<String> {
         match_ast! {
             match node {
                 ast::Path(p) => {
-                    results.push(p.to_string())
+                    if !is_generic_param_path(&p) {
+                        if let Some(path_text) = resolve_self_path_text(&p) {
+                            results.push(path_text)
+                        }
+                    }
                 },
                 _ => ()
             }
@@ -18,45 +23,705 @@ pub fn get_paths_str_from_ast_node(node: impl ast::AstNode) -> Vec<String> {
     results
 }
 
+pub fn get_signature_paths_str(node: impl ast::AstNode) -> Vec<String> {
+    // paths in params and return types, but the ones behind `dyn` and `impl`, which are interface usages with their bindings
+    node.syntax()
+        .descendants()
+        .filter_map(ast::Path::cast)
+        .filter(|p| !p.syntax().ancestors().any(|a| matches!(a.kind(), SyntaxKind::DYN_TRAIT_TYPE | SyntaxKind::IMPL_TRAIT_TYPE)))
+        // qualifiers are no types of their own, like the `Self` of `Self::Item`
+        .filter(|p| p.syntax().parent().and_then(ast::Path::cast).is_none())
+        .filter(|p| !is_generic_param_path(p))
+        .filter_map(|p| resolve_self_path_text(&p))
+        .collect()
+}
+
+pub fn get_field_relations(owner_name: &str, role: &str, ty: Option<ast::Type>) -> Vec<UMLEntity> {
+    // owned values are composed, shared and borrowed ones aggregated, weak ones only associated
+    let mut targets: Vec<(String, UMLRelationKind, Multiplicity)> = vec![];
+    walk_field_type(ty, UMLRelationKind::UMLComposition, Multiplicity::one(), &mut targets);
+    targets
+        .into_iter()
+        .map(|(t, kind, m)| {
+            // a part belongs to exactly one whole
+            let from_multiplicity = match kind {
+                UMLRelationKind::UMLComposition => Some("1"),
+                _ => None
+            };
+            let mut rel = UMLRelation::new_with_multiplicities(owner_name, &t, kind, from_multiplicity, Some(&m.to_string()));
+            rel.set_role(role);
+            UMLEntity::UMLRelation(rel)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Multiplicity {
+    lower: String,
+    // `None` stands for `*`
+    upper: Option<String>
+}
+
+impl Multiplicity {
+    fn one() -> Self {
+        Multiplicity { lower: String::from("1"), upper: Some(String::from("1")) }
+    }
+
+    fn optional() -> Self {
+        Multiplicity { lower: String::from("0"), upper: Some(String::from("1")) }
+    }
+
+    fn many() -> Self {
+        Multiplicity { lower: String::from("0"), upper: None }
+    }
+
+    fn exactly(n: &str) -> Self {
+        Multiplicity { lower: String::from(n), upper: Some(String::from(n)) }
+    }
+
+    fn nest(&self, inner: &Multiplicity) -> Self {
+        // `Option<Vec<T>>` is `0..*`, `[[T; 2]; 3]` is `6`
+        Multiplicity {
+            lower: multiply_bound(&self.lower, &inner.lower),
+            upper: match (&self.upper, &inner.upper) {
+                (Some(a), Some(b)) => Some(multiply_bound(a, b)),
+                _ => None
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Multiplicity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.upper {
+            Some(upper) if upper == &self.lower => write!(f, "{}", upper),
+            Some(upper) => write!(f, "{}..{}", self.lower, upper),
+            None => write!(f, "{}..*", self.lower)
+        }
+    }
+}
+
+fn multiply_bound(a: &str, b: &str) -> String {
+    match (a, b) {
+        ("0", _) | (_, "0") => String::from("0"),
+        ("1", x) | (x, "1") => String::from(x),
+        _ => match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => (a * b).to_string(),
+            _ => format!("{}*{}", a, b)
+        }
+    }
+}
+
+fn walk_field_type(ty: Option<ast::Type>, kind: UMLRelationKind, multiplicity: Multiplicity, targets: &mut Vec<(String, UMLRelationKind, Multiplicity)>) {
+    // the kind gets weaker going inside of pointers, never stronger
+    let ty = match ty {
+        Some(ty) => ty,
+        None => return
+    };
+    match ty {
+        ast::Type::RefType(rt) => walk_field_type(rt.ty(), kind.min(UMLRelationKind::UMLAggregation), multiplicity, targets),
+        ast::Type::PtrType(pt) => walk_field_type(pt.ty(), kind.min(UMLRelationKind::UMLAggregation), multiplicity, targets),
+        ast::Type::ParenType(pt) => walk_field_type(pt.ty(), kind, multiplicity, targets),
+        ast::Type::ArrayType(at) => {
+            let len = at.expr().map(|e| e.to_string()).unwrap_or_default();
+            walk_field_type(at.ty(), kind, multiplicity.nest(&Multiplicity::exactly(&len)), targets)
+        },
+        ast::Type::SliceType(st) => walk_field_type(st.ty(), kind, multiplicity.nest(&Multiplicity::many()), targets),
+        ast::Type::TupleType(tt) => tt.fields().for_each(|t| walk_field_type(Some(t), kind.clone(), multiplicity.clone(), targets)),
+        ast::Type::DynTraitType(dt) => {
+            dt.type_bound_list().into_iter().flat_map(|tbl| tbl.bounds()).for_each(|b| walk_field_type(b.ty(), kind.clone(), multiplicity.clone(), targets));
+        },
+        ast::Type::ImplTraitType(it) => {
+            it.type_bound_list().into_iter().flat_map(|tbl| tbl.bounds()).for_each(|b| walk_field_type(b.ty(), kind.clone(), multiplicity.clone(), targets));
+        },
+        ast::Type::PathType(pt) => {
+            let path = match pt.path() {
+                Some(path) => path,
+                None => return
+            };
+            let segment = match path.segment() {
+                Some(segment) => segment,
+                None => return
+            };
+            let name = segment.name_ref().map(|n| n.text().to_string()).unwrap_or_default();
+            let inner_kind = match name.as_str() {
+                "Rc" | "Arc" => kind.clone().min(UMLRelationKind::UMLAggregation),
+                "Weak" => kind.clone().min(UMLRelationKind::UMLAssociationUni),
+                _ => kind.clone()
+            };
+            let inner_multiplicity = match name.as_str() {
+                // `Result` is left at the multiplicity around it, its ok and error types are no optional fields
+                "Option" | "Weak" => multiplicity.nest(&Multiplicity::optional()),
+                n if COLLECTION_NAMES.contains(&n) => multiplicity.nest(&Multiplicity::many()),
+                _ => multiplicity.clone()
+            };
+            // wrappers and containers are only looked through
+            if !WRAPPER_NAMES.contains(&name.as_str()) {
+                if let Some(path_name) = get_path_name(&path) {
+                    targets.push((path_name, kind, multiplicity));
+                }
+            }
+            if let Some(gal) = segment.generic_arg_list() {
+                for ga in gal.generic_args() {
+                    if let ast::GenericArg::TypeArg(ta) = ga {
+                        walk_field_type(ta.ty(), inner_kind.clone(), inner_multiplicity.clone(), targets);
+                    }
+                }
+            }
+        },
+        _ => ()
+    }
+}
+
+const COLLECTION_NAMES: [&str; 8] = [
+    "BTreeMap", "BTreeSet", "BinaryHeap", "HashMap", "HashSet", "LinkedList", "Vec", "VecDeque",
+];
+
+const WRAPPER_NAMES: [&str; 21] = [
+    "Arc", "BTreeMap", "BTreeSet", "BinaryHeap", "Box", "Cell", "Cow", "HashMap", "HashSet", "LinkedList", "Mutex",
+    "Option", "PhantomData", "Pin", "Rc", "RefCell", "Result", "RwLock", "Vec", "VecDeque", "Weak",
+];
+
 pub fn strip_trait_bound(s: &str) -> String {
     let class_name: Vec<&str> = s.split(r"<").collect();
     String::from(class_name[0])
 }
 
-pub fn get_fn_full_name(f: &ast::Fn) -> String {
-    // include param list, return type
-    let f_name = f.name().unwrap().text().to_string();
-    let mut full_name: String = f_name.clone();
+pub fn get_trait_bound_names(tbl: &ast::TypeBoundList) -> Vec<String> {
+    // only trait bounds, lifetimes and `?Sized` are left out
+    tbl.bounds()
+        .filter(|b| b.question_mark_token().is_none())
+        .filter_map(|b| b.ty())
+        .map(|ty| strip_trait_bound(&ty.to_string()))
+        .collect()
+}
 
-    // visit all Fn descendants and process CallExpr
-    for node in f.syntax().descendants() {
-        match_ast! {
-            match node {
-                ast::ParamList(pl) => {
-                    full_name.push_str(&pl.to_string());
+pub fn get_blanket_param_name(ip: &ast::Impl) -> Option<String> {
+    // the self type is one of the impl's type params, maybe behind a reference or a pointer
+    let mut ty = ip.self_ty()?;
+    loop {
+        ty = match ty {
+            ast::Type::RefType(rt) => rt.ty()?,
+            ast::Type::PtrType(pt) => pt.ty()?,
+            ast::Type::ParenType(pt) => pt.ty()?,
+            _ => break
+        };
+    }
+    let path = match ty {
+        ast::Type::PathType(pt) => pt.path()?,
+        _ => return None
+    };
+    if path.qualifier().is_some() || path.segment()?.generic_arg_list().is_some() {
+        return None
+    }
+    let name = path.segment()?.name_ref()?.text().to_string();
+    ip.generic_param_list()?
+        .generic_params()
+        .any(|gp| matches!(gp, ast::GenericParam::TypeParam(tp) if tp.name().map(|n| n.text().to_string()) == Some(name.clone())))
+        .then_some(name)
+}
+
+pub fn get_blanket_class_name(trait_name: &str, self_ty: &str) -> String {
+    // the reserved prefix keeps it apart from the crate's types, and `impl<T> Named for &T` from the one for `T`
+    let self_ty_text = self_ty.replace('&', "ref ").replace('*', "ptr ");
+    let self_ty_words: Vec<&str> = self_ty_text
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    ["__blanket_", &trait_name.replace("::", "_"), "_for_", &self_ty_words.join("_")].concat()
+}
+
+pub fn get_generic_param_names(item: &impl HasGenericParams) -> Vec<String> {
+    // type params, lifetimes and const generics, with bounds declared inline and in where clause
+    let mut params: Vec<(String, Vec<String>)> = vec![];
+    if let Some(gpl) = item.generic_param_list() {
+        for gp in gpl.generic_params() {
+            match gp {
+                ast::GenericParam::TypeParam(tp) => {
+                    params.push((tp.name().unwrap().text().to_string(), get_bound_texts(tp.type_bound_list())));
                 },
-                ast::RetType(rt) => {
-                    full_name.push_str(" ");
-                    full_name.push_str(&rt.to_string());
+                ast::GenericParam::LifetimeParam(lp) => {
+                    params.push((lp.lifetime().unwrap().to_string(), get_bound_texts(lp.type_bound_list())));
                 },
-                _ => {
-                    // println!("{:?}", node);
-                    // println!("{}", node)
+                ast::GenericParam::ConstParam(cp) => {
+                    params.push((format!("const {}: {}", cp.name().unwrap().text(), cp.ty().unwrap()), vec![]));
                 },
             }
         }
     }
+    for wp in get_where_preds(item) {
+        let target = match (wp.ty(), wp.lifetime()) {
+            (Some(ty), _) => ty.to_string(),
+            (None, Some(lt)) => lt.to_string(),
+            (None, None) => continue
+        };
+        // bounds on other types, like `Vec<T>: Debug`, are no template params, their traits are only depended on
+        let mut bounds = get_bound_texts(wp.type_bound_list());
+        if let Some((_, p_bounds)) = params.iter_mut().find(|(p, _)| p == &target) {
+            p_bounds.append(&mut bounds);
+        }
+    }
+
+    params
+        .into_iter()
+        .map(|(p, bounds)| match bounds.is_empty() {
+            true => p,
+            false => format!("{}: {}", p, bounds.join(" + "))
+        })
+        .collect()
+}
+
+pub fn get_generic_bound_names(item: &impl HasGenericParams) -> Vec<String> {
+    // traits used as bounds of generic params, inline and in where clause
+    let mut results = vec![];
+    if let Some(gpl) = item.generic_param_list() {
+        gpl.generic_params()
+            .filter_map(|gp| match gp {
+                ast::GenericParam::TypeParam(tp) => tp.type_bound_list(),
+                _ => None
+            })
+            .for_each(|tbl| results.append(&mut get_trait_bound_names(&tbl)));
+    }
+    get_where_preds(item)
+        .iter()
+        .filter_map(|wp| wp.type_bound_list())
+        .for_each(|tbl| results.append(&mut get_trait_bound_names(&tbl)));
+    results
+}
+
+fn get_where_preds(item: &impl HasGenericParams) -> Vec<ast::WherePred> {
+    // `where Self: ...` declares supertraits rather than bounds of generic params
+    match item.where_clause() {
+        Some(wc) => wc.predicates()
+            .filter(|wp| wp.ty().map(|ty| ty.to_string()) != Some(String::from("Self")))
+            .collect(),
+        None => vec![]
+    }
+}
+
+fn get_bound_texts(tbl: Option<ast::TypeBoundList>) -> Vec<String> {
+    match tbl {
+        Some(tbl) => tbl.bounds().map(|b| b.to_string()).collect(),
+        None => vec![]
+    }
+}
+
+pub fn get_visibility(vis: Option<ast::Visibility>) -> UMLVisibility {
+    match vis.map(|v| v.kind()) {
+        Some(VisibilityKind::Pub) => UMLVisibility::Public,
+        Some(VisibilityKind::PubCrate) => UMLVisibility::Crate,
+        Some(VisibilityKind::PubSuper) | Some(VisibilityKind::In(_)) => UMLVisibility::Restricted,
+        Some(VisibilityKind::PubSelf) | None => UMLVisibility::Private,
+    }
+}
+
+pub fn get_doc_text(item: &impl HasDocComments) -> Option<String> {
+    get_doc_text_from_node(item.syntax())
+}
+
+pub fn get_doc_text_from_node(node: &SyntaxNode) -> Option<String> {
+    // `///` or `//!` comments directly on the node, one line each without the leading space
+    let lines: Vec<String> = DocCommentIter::from_syntax_node(node)
+        .filter_map(|c| c.doc_comment().map(|d| d.strip_prefix(' ').unwrap_or(d).trim_end().to_string()))
+        .collect();
+    match lines.is_empty() {
+        true => None,
+        false => Some(lines.join("\n"))
+    }
+}
+
+pub fn get_derive_names(item: &impl HasAttrs) -> Vec<String> {
+    // trait paths listed in every `#[derive(...)]`, kept as written
+    item.attrs()
+        .filter_map(|attr| attr.as_simple_call())
+        .filter(|(name, _)| name == "derive")
+        .flat_map(|(_, tt)| {
+            let tt_text = tt.syntax().text().to_string();
+            tt_text
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .split(',')
+                .map(|d| d.split_whitespace().collect::<String>())
+                .filter(|d| !d.is_empty())
+                .collect::<Vec<String>>()
+        })
+        .collect()
+}
+
+pub fn get_fn_full_name(f: &ast::Fn) -> String {
+    // include param list, return type
+    // only the Fn's own param list and return type, closures in the body have them too
+    let mut full_name: String = f.name().unwrap().text().to_string();
+    if let Some(pl) = f.param_list() {
+        full_name.push_str(&pl.to_string());
+    }
+    if let Some(rt) = f.ret_type() {
+        full_name.push(' ');
+        full_name.push_str(&rt.to_string());
+    }
+    full_name
+}
+
+pub fn get_fn_receiver(f: &ast::Fn) -> Option<UMLReceiver> {
+    // typed receivers like `self: Box<Self>` keep their type
+    let self_param = f.param_list()?.self_param()?;
+    if let Some(ty) = self_param.ty() {
+        return Some(UMLReceiver::Typed(ty.to_string()))
+    }
+    match self_param.kind() {
+        ast::SelfParamKind::Owned => Some(UMLReceiver::Value),
+        ast::SelfParamKind::Ref => Some(UMLReceiver::Ref),
+        ast::SelfParamKind::MutRef => Some(UMLReceiver::RefMut),
+    }
+}
+
+pub fn get_fn_qualifiers(f: &ast::Fn) -> Vec<UMLQualifier> {
+    // the ABI is written as a string literal, its quotes are left out
+    let mut qualifiers = vec![];
+    if f.const_token().is_some() {
+        qualifiers.push(UMLQualifier::Const);
+    }
+    if f.async_token().is_some() {
+        qualifiers.push(UMLQualifier::Async);
+    }
+    if f.unsafe_token().is_some() {
+        qualifiers.push(UMLQualifier::Unsafe);
+    }
+    if let Some(abi) = f.abi() {
+        let abi_name = abi.to_string().trim_start_matches("extern").trim().trim_matches('"').to_string();
+        qualifiers.push(UMLQualifier::Extern(Some(abi_name).filter(|a| !a.is_empty())));
+    }
+    qualifiers
+}
+
+pub fn get_interface_usages(f: &ast::Fn) -> Vec<(String, Vec<String>)> {
+    // traits behind `dyn` and `impl` in the params and the return type, with their associated type bindings
+    let mut usages = vec![];
+    let signature_nodes = f.param_list().map(|pl| pl.syntax().clone())
+        .into_iter()
+        .chain(f.ret_type().map(|rt| rt.syntax().clone()));
+    for node in signature_nodes.flat_map(|n| n.descendants()) {
+        let tbl = match_ast! {
+            match node {
+                ast::DynTraitType(dt) => dt.type_bound_list(),
+                ast::ImplTraitType(it) => it.type_bound_list(),
+                _ => None
+            }
+        };
+        for bound in tbl.iter().flat_map(|tbl| tbl.bounds()).filter(|b| b.question_mark_token().is_none()) {
+            let path = match bound.ty() {
+                Some(ast::Type::PathType(pt)) => pt.path(),
+                _ => None
+            };
+            if let Some(name) = path.as_ref().and_then(get_path_name) {
+                let bindings: Vec<String> = path
+                    .and_then(|p| p.segment())
+                    .and_then(|s| s.generic_arg_list())
+                    .iter()
+                    .flat_map(|gal| gal.generic_args())
+                    .filter_map(|ga| match ga {
+                        ast::GenericArg::AssocTypeArg(at) => Some(at.to_string()),
+                        _ => None
+                    })
+                    .collect();
+                usages.push((name, bindings));
+            }
+        }
+    }
+    usages
+}
+
+pub fn get_assoc_type_full_name(ta: &ast::TypeAlias) -> String {
+    // include bounds and default type
+    let mut full_name: String = format!("type {}", ta.name().unwrap().text());
+    if let Some(tbl) = ta.type_bound_list() {
+        full_name.push_str(&format!(": {}", tbl));
+    }
+    if let Some(ty) = ta.ty() {
+        full_name.push_str(&format!(" = {}", ty));
+    }
+    full_name
+}
+
+pub fn get_assoc_const_full_name(c: &ast::Const) -> String {
+    // include type and default value
+    let mut full_name: String = format!("const {}", c.name().unwrap().text());
+    if let Some(ty) = c.ty() {
+        full_name.push_str(&format!(": {}", ty));
+    }
+    if let Some(body) = c.body() {
+        full_name.push_str(&format!(" = {}", body));
+    }
+    full_name
+}
+
+pub fn get_variant_full_name(v: &ast::Variant) -> String {
+    // include tuple or record payload and discriminant
+    let mut full_name: String = v.name().unwrap().text().to_string();
+
+    match v.field_list() {
+        Some(ast::FieldList::TupleFieldList(tfl)) => {
+            let fields: Vec<String> = tfl.fields().map(|tf| tf.to_string()).collect();
+            full_name.push_str(&format!("({})", fields.join(", ")));
+        },
+        Some(ast::FieldList::RecordFieldList(rfl)) => {
+            let fields: Vec<String> = rfl.fields().map(|rf| rf.to_string()).collect();
+            full_name.push_str(&format!(" {{ {} }}", fields.join(", ")));
+        },
+        None => ()
+    }
+    if let Some(expr) = v.expr() {
+        full_name.push_str(&format!(" = {}", expr));
+    }
     full_name
 }
 
-pub fn get_call_expr_fn_names(call_exp: ast::CallExpr) -> String {
+pub fn get_call_expr_fn_names(call_exp: ast::CallExpr) -> Option<String> {
+    // calls on generic params, like `T::default()`, are left out
+    if let Some(ast::Expr::PathExpr(pe)) = call_exp.expr() {
+        let path = pe.path()?;
+        if is_generic_param_path(&path) {
+            return None
+        }
+        // associated fns, like `Hello::new()`, are owned by their type, which is named in camel case unlike modules
+        if let Some(qualifier) = path.qualifier() {
+            let is_type = qualifier.segment()
+                .map(|s| s.self_type_token().is_some() || s.name_ref().map(|n| n.text().starts_with(char::is_uppercase)).unwrap_or(false))
+                .unwrap_or(false);
+            if is_type {
+                return get_path_name(&qualifier)
+            }
+        }
+    }
     let call_expr = call_exp.to_string();
     let call_names: Vec<&str> = call_expr.split("(").collect();
-    String::from(call_names[0])
+    Some(String::from(call_names[0]))
+}
+
+pub fn get_method_call_type_names(f: &ast::Fn, self_field_types: &[(String, String)]) -> Vec<String> {
+    // types owning the methods called on `self` fields, typed params and `let` bindings
+    let mut binding_types: Vec<(String, String)> = vec![];
+    if let Some(pl) = f.param_list() {
+        for param in pl.params() {
+            if let (Some(ast::Pat::IdentPat(ip)), Some(ty)) = (param.pat(), param.ty()) {
+                if let (Some(name), Some(type_name)) = (ip.name(), get_owning_type_name(&ty)) {
+                    binding_types.push((name.text().to_string(), type_name));
+                }
+            }
+        }
+    }
+    let body = match f.body() {
+        Some(body) => body,
+        None => return vec![]
+    };
+    for ls in body.syntax().descendants().filter_map(ast::LetStmt::cast) {
+        if let Some(ast::Pat::IdentPat(ip)) = ls.pat() {
+            // explicit type first, then constructor-like initialisers as `Type::new(..)`
+            let type_name = match ls.ty() {
+                Some(ty) => get_owning_type_name(&ty),
+                None => ls.initializer().and_then(|e| get_expr_type_name(&e))
+            };
+            if let (Some(name), Some(type_name)) = (ip.name(), type_name) {
+                binding_types.push((name.text().to_string(), type_name));
+            }
+        }
+    }
+
+    let mut results = vec![];
+    for mc in body.syntax().descendants().filter_map(ast::MethodCallExpr::cast) {
+        let type_name = match mc.receiver() {
+            Some(ast::Expr::FieldExpr(fe)) if fe.expr().map(|e| e.to_string()) == Some(String::from("self")) => {
+                fe.name_ref().and_then(|n| find_type_name(self_field_types, n.text().as_str()))
+            },
+            Some(ast::Expr::PathExpr(pe)) => {
+                pe.path().and_then(|p| p.as_single_name_ref()).and_then(|n| find_type_name(&binding_types, n.text().as_str()))
+            },
+            _ => None
+        };
+        if let Some(type_name) = type_name {
+            results.push(type_name);
+        }
+    }
+    results
+}
+
+pub fn get_struct_field_types(ip: &ast::Impl, struct_name: &str) -> Vec<(String, String)> {
+    // fields of the struct declared next to the impl, by name or position
+    let st = ip.syntax()
+        .parent()
+        .into_iter()
+        .flat_map(|p| p.children())
+        .filter_map(ast::Struct::cast)
+        .find(|st| st.name().map(|n| n.text() == struct_name).unwrap_or(false));
+    match st.and_then(|st| st.field_list()) {
+        Some(ast::FieldList::RecordFieldList(rfl)) => rfl.fields()
+            .filter_map(|rf| Some((rf.name()?.text().to_string(), get_owning_type_name(&rf.ty()?)?)))
+            .collect(),
+        Some(ast::FieldList::TupleFieldList(tfl)) => tfl.fields()
+            .enumerate()
+            .filter_map(|(idx, tf)| Some((idx.to_string(), get_owning_type_name(&tf.ty()?)?)))
+            .collect(),
+        None => vec![]
+    }
+}
+
+fn get_owning_type_name(ty: &ast::Type) -> Option<String> {
+    // methods are called through references and smart pointers on the type inside of them
+    match ty {
+        ast::Type::RefType(rt) => get_owning_type_name(&rt.ty()?),
+        ast::Type::PtrType(pt) => get_owning_type_name(&pt.ty()?),
+        ast::Type::ParenType(pt) => get_owning_type_name(&pt.ty()?),
+        ast::Type::PathType(pt) => {
+            let path = pt.path()?;
+            let segment = path.segment()?;
+            if SMART_POINTER_NAMES.contains(&segment.name_ref()?.text().as_str()) {
+                if let Some(ast::GenericArg::TypeArg(ta)) = segment.generic_arg_list()?.generic_args().next() {
+                    return get_owning_type_name(&ta.ty()?)
+                }
+            }
+            get_path_name(&path)
+        },
+        _ => None
+    }
+}
+
+fn get_expr_type_name(expr: &ast::Expr) -> Option<String> {
+    // type of `Type::new(..)` or `Type { .. }`, looking into `Box::new(..)` and alike
+    match expr {
+        ast::Expr::CallExpr(ce) => {
+            let path = match ce.expr()? {
+                ast::Expr::PathExpr(pe) => pe.path()?,
+                _ => return None
+            };
+            let qualifier = path.qualifier()?;
+            let qualifier_name = qualifier.segment()?.name_ref()?.text().to_string();
+            if SMART_POINTER_NAMES.contains(&qualifier_name.as_str()) {
+                return get_expr_type_name(&ce.arg_list()?.args().next()?)
+            }
+            get_path_name(&qualifier)
+        },
+        ast::Expr::RecordExpr(re) => get_path_name(&re.path()?),
+        _ => None
+    }
+}
+
+fn get_path_name(path: &ast::Path) -> Option<String> {
+    // path segments without their generic args, up to the path itself only
+    // generic params are no types to name
+    if is_generic_param_path(path) {
+        return None
+    }
+    let mut names: Vec<String> = std::iter::successors(Some(path.clone()), |p| p.qualifier())
+        .map(|p| p.segment().map(|s| get_segment_name(&s, path.syntax())))
+        .collect::<Option<Option<Vec<String>>>>()??;
+    names.reverse();
+    Some(names.join("."))
+}
+
+fn get_segment_name(segment: &ast::PathSegment, node: &SyntaxNode) -> Option<String> {
+    // `Self` is named after the type it stands for
+    match segment.self_type_token() {
+        Some(_) => get_self_type_name(node),
+        None => segment.name_ref().map(|n| n.text().to_string())
+    }
+}
+
+fn resolve_self_path_text(path: &ast::Path) -> Option<String> {
+    // path text with a leading `Self` replaced by the type it stands for
+    let path_text = path.to_string();
+    match path_text.strip_prefix("Self") {
+        Some(rest) if rest.is_empty() || rest.starts_with("::") || rest.starts_with('<') => {
+            Some([get_self_type_name(path.syntax())?, String::from(rest)].concat())
+        },
+        _ => Some(path_text)
+    }
+}
+
+fn get_self_type_name(node: &SyntaxNode) -> Option<String> {
+    // the impl's self type or the enclosing trait or type, blanket impls' `Self` is just a generic param
+    node.ancestors().find_map(|n| match_ast! {
+        match n {
+            ast::Impl(it) => match get_blanket_param_name(&it) {
+                Some(_) => Some(None),
+                None => Some(it.self_ty().map(|ty| strip_trait_bound(&ty.to_string())))
+            },
+            ast::Trait(it) => Some(it.name().map(|n| n.text().to_string())),
+            ast::Struct(it) => Some(it.name().map(|n| n.text().to_string())),
+            ast::Enum(it) => Some(it.name().map(|n| n.text().to_string())),
+            ast::Union(it) => Some(it.name().map(|n| n.text().to_string())),
+            _ => None
+        }
+    })?
+}
+
+fn is_generic_param_path(path: &ast::Path) -> bool {
+    // the path starts with a generic param in scope, like `T` or `T::Item`
+    let first_name = std::iter::successors(Some(path.clone()), |p| p.qualifier())
+        .last()
+        .and_then(|p| p.segment())
+        .and_then(|s| s.name_ref())
+        .map(|n| n.text().to_string());
+    match first_name {
+        Some(name) => get_generic_params_in_scope(path.syntax()).contains(&name),
+        None => false
+    }
+}
+
+fn get_generic_params_in_scope(node: &SyntaxNode) -> Vec<String> {
+    // type and const params of the method, the impl and the item around the node
+    node.ancestors()
+        .filter_map(|n| match_ast! {
+            match n {
+                ast::Fn(it) => it.generic_param_list(),
+                ast::Impl(it) => it.generic_param_list(),
+                ast::Struct(it) => it.generic_param_list(),
+                ast::Enum(it) => it.generic_param_list(),
+                ast::Union(it) => it.generic_param_list(),
+                ast::Trait(it) => it.generic_param_list(),
+                ast::TypeAlias(it) => it.generic_param_list(),
+                _ => None
+            }
+        })
+        .flat_map(|gpl| gpl.generic_params())
+        .filter_map(|gp| match gp {
+            ast::GenericParam::TypeParam(tp) => tp.name(),
+            ast::GenericParam::ConstParam(cp) => cp.name(),
+            ast::GenericParam::LifetimeParam(_) => None
+        })
+        .map(|n| n.text().to_string())
+        .collect()
+}
+
+fn find_type_name(types: &[(String, String)], name: &str) -> Option<String> {
+    // the last binding shadows earlier ones
+    types.iter().rev().find(|(n, _)| n == name).map(|(_, t)| t.clone())
+}
+
+const SMART_POINTER_NAMES: [&str; 3] = ["Box", "Rc", "Arc"];
+
+pub fn get_macro_call_names(node: &SyntaxNode) -> Vec<String> {
+    // names of the invoked macros, well-known std ones with their full paths
+    let mut names: Vec<String> = node.descendants()
+        .filter_map(ast::MacroCall::cast)
+        .filter_map(|mc| mc.path()?.segment()?.name_ref())
+        .map(|n| n.text().to_string())
+        .collect();
+    // calls nested in other macros' arguments are only tokens, as `name` followed by `!`
+    names.extend(
+        node.descendants_with_tokens()
+            .filter_map(|e| e.into_token())
+            .filter(|t| t.kind() == SyntaxKind::IDENT && t.parent().map(|p| p.kind()) == Some(SyntaxKind::TOKEN_TREE))
+            .filter(|t| t.next_token().map(|n| n.kind()) == Some(SyntaxKind::BANG))
+            .map(|t| t.text().to_string())
+    );
+    names
+        .into_iter()
+        .map(|n| UMLMacro::get_std_path(&n).unwrap_or(n))
+        .collect()
 }
 
 pub fn replace_coloncolon_path(cc_path: &str) -> String {
-    String::from(cc_path.replace("::", "."))
+    cc_path.replace("::", ".")
 }
 
 #[cfg(test)]
@@ -67,4 +732,28 @@ mod tests {
     fn test_replace_coloncolon_path() {
         assert_eq!(replace_coloncolon_path("Mock::new"), String::from("Mock.new"));
     }
+
+    #[test]
+    fn test_get_method_call_type_names() {
+        let code: &str = r#"
+        struct Service { store: std::sync::Arc<Store>, cache: &'static Cache }
+        impl Service {
+            fn run(&self, client: &Client) {
+                self.store.insert(0);
+                self.cache.clear();
+                client.send();
+                let conn = Conn { id: 0 };
+                conn.close();
+                self.other();
+            }
+        }
+        "#;
+        let file = ra_ap_syntax::SourceFile::parse(code).tree();
+        let ip = file.syntax().descendants().find_map(ast::Impl::cast).unwrap();
+        let f = ip.syntax().descendants().find_map(ast::Fn::cast).unwrap();
+        let field_types = get_struct_field_types(&ip, "Service");
+
+        assert_eq!(field_types, vec![(String::from("store"), String::from("Store")), (String::from("cache"), String::from("Cache"))]);
+        assert_eq!(get_method_call_type_names(&f, &field_types), vec!["Store", "Cache", "Client", "Conn"]);
+    }
 }
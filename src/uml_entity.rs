@@ -1,6 +1,12 @@
 
 mod uml_fn;
+mod uml_field;
+mod uml_visibility;
+mod uml_receiver;
+mod uml_qualifier;
 mod uml_class;
+mod uml_macro;
+mod uml_static;
 mod uml_relation;
 mod uml_graph;
 mod uml_outer_entity;
@@ -8,7 +14,13 @@ mod base;
 
 pub use {
     uml_fn::UMLFn,
+    uml_field::UMLField,
+    uml_visibility::UMLVisibility,
+    uml_receiver::UMLReceiver,
+    uml_qualifier::UMLQualifier,
     uml_class::{UMLClass, UMLClassKind},
+    uml_macro::UMLMacro,
+    uml_static::UMLStatic,
     uml_relation::{UMLRelation, UMLRelationKind},
     uml_graph::UMLGraph,
     uml_outer_entity::UMLOuterEntity,
@@ -33,7 +45,42 @@ mod tests {
         // relations can be added if both sides on the scope
         uml_graph.add_fn(UMLFn::new("main", "main()"));
         assert_eq!(uml_graph.relations().len(), 1);
-        assert_eq!(uml_graph.relations().get(0).unwrap().from, "main");
+        assert_eq!(uml_graph.relations().first().unwrap().from, "main");
+    }
+
+    #[test]
+    fn test_add_relations_with_roles() {
+        let mut uml_graph = UMLGraph::new("");
+        uml_graph.add_struct(UMLClass::new("Main", vec![], vec![], UMLClassKind::UMLClass));
+        uml_graph.add_struct(UMLClass::new("Db", vec![], vec![], UMLClassKind::UMLClass));
+        uml_graph.add_relation(UMLRelation::new("Main", "Db", UMLRelationKind::UMLDependency));
+        for role in ["primary", "replica", "primary"] {
+            let mut rel = UMLRelation::new("Main", "Db", UMLRelationKind::UMLComposition);
+            rel.set_role(role);
+            uml_graph.add_relation(rel);
+        }
+
+        // one relation per role, the dependency without role is taken over
+        let roles: Vec<Option<&str>> = uml_graph.relations().iter().map(|r| r.to_role.as_deref()).collect();
+        assert_eq!(roles, vec![Some("primary"), Some("replica")]);
+    }
+
+    #[test]
+    fn test_external_relations() {
+        let mut uml_graph = UMLGraph::new("");
+        uml_graph.add_struct(UMLClass::new("Mock", vec![], vec![], UMLClassKind::UMLClass));
+        uml_graph.add_struct(UMLClass::new("Local", vec![], vec![], UMLClassKind::UMLClass));
+        uml_graph.add_outer_entity(UMLOuterEntity::new("fmt", "std"));
+        uml_graph.add_relation(UMLRelation::new("Mock", "fmt.Formatter", UMLRelationKind::UMLAggregation));
+        uml_graph.add_relation(UMLRelation::new("Mock", "Clone", UMLRelationKind::UMLRealization));
+        uml_graph.add_relation(UMLRelation::new("Mock", "crate.db.Store", UMLRelationKind::UMLComposition));
+        uml_graph.add_relation(UMLRelation::new("Mock", "Local", UMLRelationKind::UMLComposition));
+        uml_graph.add_relation(UMLRelation::new("Mock", "Unknown", UMLRelationKind::UMLComposition));
+        uml_graph.add_relation(UMLRelation::new("Mock", "serde.Serialize", UMLRelationKind::UMLDependency));
+
+        // named through the imports and the prelude, the crate's own paths and dependencies are left out
+        let targets: Vec<String> = uml_graph.external_relations().into_iter().map(|r| r.to).collect();
+        assert_eq!(targets, vec![String::from("std.fmt.Formatter"), String::from("std.clone.Clone")]);
     }
 
     #[test]
@@ -49,8 +96,8 @@ mod tests {
         uml_graph.add_outer_entity(UMLOuterEntity::new("outer_mock", "mock"));
         assert_eq!(uml_graph.outer_relations().len(), 1);
         assert_eq!(uml_graph.relations().len(), 0);
-        assert_eq!(uml_graph.outer_relations().get(0).unwrap().from, "Main");
-        assert_eq!(uml_graph.outer_relations().get(0).unwrap().to, "mock.outer_mock");
+        assert_eq!(uml_graph.outer_relations().first().unwrap().from, "Main");
+        assert_eq!(uml_graph.outer_relations().first().unwrap().to, "mock.outer_mock");
     }
 
     #[test]
@@ -63,4 +110,15 @@ mod tests {
         assert_eq!(uml_graph.outer_relations().len(), 1);
         assert_eq!(uml_graph.outer_relations()[0], UMLRelation::new("mock", "Mod1.outer_entity", UMLRelationKind::UMLDependency));
     }
+
+    #[test]
+    fn test_fn_receivers() {
+        assert_eq!(UMLFn::from_full_name("new() -> Self").receiver, None);
+        assert_eq!(UMLFn::from_full_name("from(value: T) -> Self").receiver, None);
+        assert_eq!(UMLFn::from_full_name("into_inner(mut self) -> T").receiver, Some(UMLReceiver::Value));
+        assert_eq!(UMLFn::from_full_name("get(&'a self, idx: usize) -> &T").receiver, Some(UMLReceiver::Ref));
+        assert_eq!(UMLFn::from_full_name("set(&mut self, value: T)").receiver, Some(UMLReceiver::RefMut));
+        assert_eq!(UMLFn::from_full_name("boxed(self: Box<Self>)").receiver, Some(UMLReceiver::Typed(String::from("Box<Self>"))));
+        assert!(UMLFn::from_full_name("new() -> Self").is_associated());
+    }
 }
@@ -1,9 +1,12 @@
-use super::{UMLClass, UMLFn, UMLRelation, UMLOuterEntity};
+use super::{UMLClass, UMLFn, UMLMacro, UMLStatic, UMLRelation, UMLOuterEntity};
 
+#[allow(clippy::enum_variant_names)]
 pub enum UMLEntity {
-    // UML Entity has three types, Class, Function and Relation
+    // UML Entity has three types, Class, Function and Relation, besides macros, statics and outer entities
     UMLClass(UMLClass),
     UMLFn(UMLFn),
+    UMLMacro(UMLMacro),
+    UMLStatic(UMLStatic),
     UMLRelation(UMLRelation),
     UMLOuterEntity(UMLOuterEntity),
 }
\ No newline at end of file
@@ -1,36 +1,132 @@
+use super::{UMLFn, UMLField, UMLVisibility};
 
+#[allow(clippy::enum_variant_names)]
 #[derive(PartialEq, Debug, Clone)]
 pub enum UMLClassKind {
-    // In Rust, the UML class could be further be categorized as class or trait
+    // In Rust, the UML class could be further be categorized as class, trait or enumeration
     UMLClass,
     UMLTrait,
+    UMLEnum,
+    // the "any T" a blanket impl is for, like in `impl<T: Display> ToPretty for T`, with the self type
+    UMLBlanket(String),
 }
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct UMLClass {
     pub name: String,
-    method_names: Vec<String>,
-    fields: Vec<String>,
-    pub kind: UMLClassKind
+    methods: Vec<UMLFn>,
+    fields: Vec<UMLField>,
+    variants: Vec<String>,
+    generics: Vec<String>,
+    derives: Vec<String>,
+    trait_methods: Vec<(String, Vec<UMLFn>)>,
+    pub kind: UMLClassKind,
+    pub visibility: UMLVisibility,
+    pub doc: Option<String>
 }
 
 
 impl UMLClass {
     pub fn new(name: &str, fields: Vec<String>, method_names: Vec<String>, kind: UMLClassKind) -> UMLClass {
-        UMLClass { name: String::from(name), fields: fields, method_names: method_names, kind: kind}
+        let mut fields: Vec<UMLField> = fields.iter().map(|f| UMLField::from_full_name(f)).collect();
+        let mut methods: Vec<UMLFn> = method_names.iter().map(|m| UMLFn::from_full_name(m)).collect();
+        if kind == UMLClassKind::UMLTrait {
+            // trait members are always as public as the trait
+            fields.iter_mut().for_each(|f| f.visibility = UMLVisibility::Public);
+            methods.iter_mut().for_each(|m| m.visibility = UMLVisibility::Public);
+        }
+        UMLClass { name: String::from(name), fields, methods, variants: vec![], generics: vec![], derives: vec![], trait_methods: vec![], kind, visibility: UMLVisibility::default(), doc: None }
     }
 
-    pub fn merge_method_names_from(&mut self, from: &mut UMLClass) -> () {
+    pub fn new_enum(name: &str, variants: Vec<String>, method_names: Vec<String>) -> UMLClass {
+        let methods = method_names.iter().map(|m| UMLFn::from_full_name(m)).collect();
+        UMLClass { name: String::from(name), fields: vec![], methods, variants, generics: vec![], derives: vec![], trait_methods: vec![], kind: UMLClassKind::UMLEnum, visibility: UMLVisibility::default(), doc: None }
+    }
+
+    pub fn merge_method_names_from(&mut self, from: &mut UMLClass) {
         // merge methods from another UML Class
-        if self.name != from.name {()}
-        self.method_names.append(&mut from.method_names);
+        if self.name != from.name {
+            return
+        }
+        // the same method could be declared by a trait and found again in an impl of it
+        for m in from.methods.drain(..) {
+            if !self.methods.iter().any(|e_m| e_m.full_name == m.full_name) {
+                self.methods.push(m);
+            }
+        }
+        // an impl could come before the declaration, which then brings the rest of the class
+        if self.fields.is_empty() && self.variants.is_empty() && self.generics.is_empty() {
+            self.fields.append(&mut from.fields);
+            self.variants.append(&mut from.variants);
+            self.generics.append(&mut from.generics);
+        }
+        if self.derives.is_empty() {
+            self.derives.append(&mut from.derives);
+        }
+        for (trait_name, mut methods) in from.trait_methods.drain(..) {
+            self.add_trait_methods(&trait_name, &mut methods);
+        }
+        if self.kind == UMLClassKind::UMLClass {
+            self.kind = from.kind.clone();
+        }
+        if self.visibility == UMLVisibility::Private {
+            self.visibility = from.visibility.clone();
+        }
+        if self.doc.is_none() {
+            self.doc = from.doc.take();
+        }
+    }
+
+    pub fn add_method(&mut self, method: UMLFn) {
+        self.methods.push(method);
+    }
+
+    pub fn get_methods(&self) -> Vec<UMLFn> {
+        self.methods.clone()
     }
 
-    pub fn get_method_names(&self) -> Vec<String> {
-        self.method_names.clone()
+    pub fn add_field(&mut self, field: UMLField) {
+        self.fields.push(field);
     }
 
-    pub fn get_field_names(&self) -> Vec<String> {
+    pub fn get_fields(&self) -> Vec<UMLField> {
         self.fields.clone()
     }
-}
\ No newline at end of file
+
+    pub fn add_trait_methods(&mut self, trait_name: &str, methods: &mut Vec<UMLFn>) {
+        // methods of the same trait impl are grouped together
+        match self.trait_methods.iter_mut().find(|(t, _)| t == trait_name) {
+            Some((_, existing)) => existing.append(methods),
+            None => self.trait_methods.push((String::from(trait_name), std::mem::take(methods)))
+        }
+    }
+
+    pub fn get_trait_methods(&self) -> Vec<(String, Vec<UMLFn>)> {
+        self.trait_methods.clone()
+    }
+
+    pub fn is_trait_impl_only(&self) -> bool {
+        // what an impl of a trait for a type brings, without the type itself
+        self.kind == UMLClassKind::UMLClass && self.fields.is_empty() && self.methods.is_empty() && self.variants.is_empty() && !self.trait_methods.is_empty()
+    }
+
+    pub fn get_variant_names(&self) -> Vec<String> {
+        self.variants.clone()
+    }
+
+    pub fn set_generic_names(&mut self, generics: Vec<String>) {
+        self.generics = generics;
+    }
+
+    pub fn get_generic_names(&self) -> Vec<String> {
+        self.generics.clone()
+    }
+
+    pub fn set_derive_names(&mut self, derives: Vec<String>) {
+        self.derives = derives;
+    }
+
+    pub fn get_derive_names(&self) -> Vec<String> {
+        self.derives.clone()
+    }
+}
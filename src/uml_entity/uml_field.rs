@@ -0,0 +1,22 @@
+use super::UMLVisibility;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct UMLField {
+    pub name: String,
+    pub full_name: String,
+    pub visibility: UMLVisibility,
+    pub doc: Option<String>
+}
+
+impl UMLField {
+    pub fn new(name: &str, full_name: &str) -> UMLField {
+        UMLField { name: String::from(name), full_name: String::from(full_name), visibility: UMLVisibility::default(), doc: None }
+    }
+
+    pub fn from_full_name(full_name: &str) -> UMLField {
+        // field name is what comes before the type or value, after `type` or `const` of associated items
+        let declaration = full_name.split([':', '=']).next().unwrap_or_default();
+        let name = declaration.split_whitespace().last().unwrap_or_default();
+        UMLField::new(name, full_name)
+    }
+}
@@ -1,12 +1,41 @@
+use super::{UMLVisibility, UMLReceiver, UMLQualifier};
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct UMLFn {
     pub name: String,
-    pub full_name: String
+    pub full_name: String,
+    pub visibility: UMLVisibility,
+    pub doc: Option<String>,
+    // associated functions have no receiver
+    pub receiver: Option<UMLReceiver>,
+    // trait methods with a body are provided (default) ones
+    pub has_default: bool,
+    // `const`, `async`, `unsafe` and `extern` from the fn header
+    pub qualifiers: Vec<UMLQualifier>
 }
 
 impl UMLFn {
     pub fn new(name: &str, full_name: &str) -> UMLFn {
-        UMLFn { name: String::from(name), full_name: String::from(full_name) }
+        UMLFn { name: String::from(name), full_name: String::from(full_name), visibility: UMLVisibility::default(), doc: None, receiver: None, has_default: false, qualifiers: vec![] }
     }
-}
\ No newline at end of file
+
+    pub fn is_associated(&self) -> bool {
+        self.receiver.is_none()
+    }
+
+    pub fn is_unsafe(&self) -> bool {
+        self.qualifiers.contains(&UMLQualifier::Unsafe)
+    }
+
+    pub fn from_full_name(full_name: &str) -> UMLFn {
+        // fn name is what comes before generic params or param list
+        let name = full_name.split(['<', '(']).next().unwrap_or_default();
+        let mut uml_fn = UMLFn::new(name.trim(), full_name);
+        // a signature given as text has nothing but the param list to tell the receiver
+        uml_fn.receiver = full_name
+            .split_once('(')
+            .and_then(|(_, params)| params.split([',', ')']).next())
+            .and_then(UMLReceiver::from_param_text);
+        uml_fn
+    }
+}
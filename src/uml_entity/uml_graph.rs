@@ -1,6 +1,6 @@
 use {
     super::uml_fn::UMLFn,
-    super::{UMLClass},
+    super::{UMLClass, UMLMacro, UMLStatic},
     super::{UMLRelation, UMLRelationKind},
 };
 use std::collections::BTreeMap;
@@ -11,8 +11,15 @@ use super::{UMLOuterEntity};
 pub struct UMLGraph {
     // The Graph struct, one for each graph, contains all the information on it
     pub name: String,
+    pub doc: Option<String>,
+    // like `foreign` for extern blocks
+    pub stereotype: Option<String>,
     structs: Vec<UMLClass>,
     fns: Vec<UMLFn>,
+    macros: Vec<UMLMacro>,
+    statics: Vec<UMLStatic>,
+    // crates declared with `extern crate`, by the name they are used with
+    extern_crates: Vec<String>,
     relations: Vec<UMLRelation>,
     pub modules: BTreeMap<String, UMLGraph>,
     outer_entities: Vec<UMLOuterEntity>,
@@ -36,13 +43,28 @@ impl UMLGraph {
             .collect()
     }
 
+    pub fn macros(&self) -> Vec<&UMLMacro> {
+        // macros getter
+        self.macros.iter().collect()
+    }
+
+    pub fn statics(&self) -> Vec<&UMLStatic> {
+        // statics getter
+        self.statics.iter().collect()
+    }
+
+    pub fn extern_crates(&self) -> Vec<&String> {
+        // extern crates getter
+        self.extern_crates.iter().collect()
+    }
+
     pub fn relations(&self) -> Vec<&UMLRelation> {
-        // relations getter
+        // relations getter, macros can only be depended on
         self.relations
             .iter()
             .filter(|rel| {
                 (self.get_fn_names().contains(&rel.from) || self.get_struct_names().contains(&rel.from)) &&
-                (self.get_fn_names().contains(&rel.to) || self.get_struct_names().contains(&rel.to)) &&
+                (self.get_fn_names().contains(&rel.to) || self.get_struct_names().contains(&rel.to) || self.get_macro_names().contains(&rel.to)) &&
                 (!rel.to.contains(r".") && !rel.from.contains(r"."))
             })
             .collect()
@@ -64,6 +86,58 @@ impl UMLGraph {
             .collect()
     }
 
+    pub fn external_relations(&self) -> Vec<UMLRelation> {
+        // relations to traits and types of other crates, which are neither in the graph nor imported by `use`
+        // dependencies are left out, most of them come from paths in fn bodies that are no types
+        self.relations
+            .iter()
+            .filter(|rel| {
+                rel.kind != UMLRelationKind::UMLDependency &&
+                (self.get_fn_names().contains(&rel.from) || self.get_struct_names().contains(&rel.from)) &&
+                !rel.from.contains('.') && !self.is_outer_entity(&rel.to)
+            })
+            .filter_map(|rel| {
+                let to_name = self.get_external_path(&rel.to)?;
+                let mut rel_results = rel.clone();
+                rel_results.update_relation_names(&rel.from, &to_name);
+                Some(rel_results)
+            })
+            .collect()
+    }
+
+    pub fn module_relations(&self) -> Vec<UMLRelation> {
+        // relations to items of child modules by their plain paths, like `m::f()`, resolved from this module
+        self.relations
+            .iter()
+            .filter(|rel| {
+                (self.get_fn_names().contains(&rel.from) || self.get_struct_names().contains(&rel.from)) &&
+                self.is_module_item_path(&rel.to)
+            })
+            .map(|rel| {
+                let mut rel_results = rel.clone();
+                rel_results.update_relation_names(&rel.from, &["self.", &rel.to].concat());
+                rel_results
+            })
+            .collect()
+    }
+
+    pub fn std_macro_relations(&self) -> Vec<&UMLRelation> {
+        // dependencies on the well-known std macros, which are only drawn on demand
+        self.relations
+            .iter()
+            .filter(|rel| {
+                (self.get_fn_names().contains(&rel.from) || self.get_struct_names().contains(&rel.from)) &&
+                UMLMacro::is_std_path(&rel.to)
+            })
+            .collect()
+    }
+
+    pub fn is_external_path(&self, name: &str) -> bool {
+        // full paths not starting in this crate
+        let head = name.split('.').next().unwrap_or_default();
+        !["crate", "self", "super"].contains(&head) && !self.modules.contains_key(head) && name.contains('.')
+    }
+
     fn get_struct_names(&self) -> Vec<String> {
         // struct names getter
         self.structs
@@ -72,6 +146,14 @@ impl UMLGraph {
             .collect()
     }
 
+    fn get_macro_names(&self) -> Vec<String> {
+        // macro names getter
+        self.macros
+            .iter()
+            .map(|m| m.name.clone())
+            .collect()
+    }
+
     fn get_fn_names(&self) -> Vec<String> {
         // function names getter
         self.fns
@@ -88,22 +170,58 @@ impl UMLGraph {
         self.structs.iter_mut().find(|st| st.name == struct_name)
     }
 
-    fn relation_mut(&mut self, from: &str, to: &str) -> Option<&mut UMLRelation> {
-        // relation mut getter
-        for rel in &mut self.relations {
-            if rel.from == from && rel.to == to {
-                return Some(rel)
-            }
+    fn relation_mut(&mut self, new_rel: &UMLRelation) -> Option<&mut UMLRelation> {
+        // relation mut getter, the roles and multiplicity only tell relations apart if both have them
+        let is_other = |a: &Option<String>, b: &Option<String>| a.is_some() && b.is_some() && a != b;
+        self.relations
+            .iter_mut()
+            .find(|rel| {
+                rel.from == new_rel.from && rel.to == new_rel.to &&
+                !(new_rel.to_role.is_some() && rel.to_role != new_rel.to_role) &&
+                !(new_rel.from_role.is_some() && rel.from_role != new_rel.from_role) &&
+                !is_other(&rel.to_multiplicity, &new_rel.to_multiplicity)
+            })
+    }
+
+    fn is_module_item_path(&self, name: &str) -> bool {
+        // path through child modules ending in one of their items
+        let (head, rest) = match name.split_once('.') {
+            Some(split) => split,
+            None => return false
+        };
+        match self.modules.get(head) {
+            Some(m) => m.is_module_item_path(rest) || m.get_struct_names().iter().chain(m.get_fn_names().iter()).chain(m.get_macro_names().iter()).any(|n| n == rest),
+            None => false
         }
-        None
     }
 
     fn is_outer_entity(&self, name: &str) -> bool {
         self.outer_entities
             .iter()
-            .map(|oe| oe.name.clone())
-            .collect::<String>()
-            .contains(name)
+            .any(|oe| oe.name == name)
+    }
+
+    fn get_external_path(&self, name: &str) -> Option<String> {
+        // full path of a name not in the graph, through the imports of its first segment or the prelude
+        if self.get_struct_names().iter().chain(self.get_fn_names().iter()).chain(self.get_macro_names().iter()).any(|n| n == name) {
+            return None
+        }
+        let (head, rest) = match name.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (name, None)
+        };
+        let full_name = match (self.is_outer_entity(head), rest) {
+            (true, Some(rest)) => [self.get_outer_entity_full_name(head).as_str(), ".", rest].concat(),
+            (false, Some(_)) => String::from(name),
+            (_, None) => {
+                // bare names not imported could only come from the prelude, or be in the crate already
+                PRELUDE_PATHS.iter().find(|(n, _)| *n == name).map(|(_, p)| String::from(*p))?
+            }
+        };
+        match self.is_external_path(&full_name) {
+            true => Some(full_name),
+            false => None
+        }
     }
 
     fn get_outer_entity_full_name(&self, name: &str) -> String {
@@ -113,7 +231,7 @@ impl UMLGraph {
                 match self.outer_entities 
                     .iter()
                     .find(|oe| oe.name == name) {
-                        Some(soe) => vec![soe.mod_name.as_str(), ".", name].concat(),
+                        Some(soe) => [soe.mod_name.as_str(), ".", name].concat(),
                         None => String::from(name)
                     }
             }
@@ -124,48 +242,55 @@ impl UMLGraph {
 impl UMLGraph {
     // Setters & Adders
     pub fn new(name: &str) -> UMLGraph {
-        UMLGraph { name: String::from(name), structs: vec![], fns: vec![], relations: vec![], modules: BTreeMap::new(), outer_entities: vec![]}
+        UMLGraph { name: String::from(name), doc: None, stereotype: None, structs: vec![], fns: vec![], macros: vec![], statics: vec![], extern_crates: vec![], relations: vec![], modules: BTreeMap::new(), outer_entities: vec![]}
     }
 
-    pub fn add_module(&mut self, module: UMLGraph) -> () {
+    pub fn add_module(&mut self, module: UMLGraph) {
         self.modules.insert(String::from(&module.name), module);
     }
 
-    pub fn add_relation(&mut self, rel: UMLRelation) -> () {
-        // if relation's from or to not in graph already, it cannot be added
-        if &rel.from != &rel.to {
-            // if new relation's kind is associationUni, then search for associationUni relation with opposite direction and replace it with associationBi
-            if &rel.kind == &UMLRelationKind::UMLAssociationUni {
-                match self.relation_mut(&rel.to, &rel.from) {
-                    Some(e_rel) => {
-                        if &e_rel.kind == &rel.kind {
-                            e_rel.change_relation_kind(UMLRelationKind::UMLAssociationBi);
-                            return
-                        }
-                    },
-                    None => ()
-                }
+    pub fn add_relation(&mut self, rel: UMLRelation) {
+        // relations with the same ends are merged, unless they play different roles or have different multiplicities
+        if let Some(e_rel) = self.relation_mut(&rel) {
+            // if existing relation's kind has less priority than new relation's, change the relation kind
+            if e_rel.kind < rel.kind {
+                e_rel.change_relation_kind(rel.kind);
+                e_rel.set_multiplicities(rel.from_multiplicity.as_deref(), rel.to_multiplicity.as_deref());
             }
-            
-            match self.relation_mut(&rel.from, &rel.to) {
-                Some(e_rel) => {
-                    // if existing relation's kind has less priority than new relation's, change the relation kind
-                    if e_rel.kind < rel.kind {
-                        e_rel.change_relation_kind(rel.kind);
-                    }
-                },
-                None => {
-                    self.relations.push(rel);
+            rel.bindings
+                .into_iter()
+                .for_each(|b| if !e_rel.bindings.contains(&b) { e_rel.bindings.push(b) });
+            return
+        }
+
+        // if new relation's kind is associationUni, then search for associationUni relation with opposite direction and replace it with associationBi
+        // self-associations of recursive types are never merged, each of them is a field of its own
+        // the ends of both directions have to play the same role, if any
+        if rel.kind == UMLRelationKind::UMLAssociationUni && rel.from != rel.to {
+            let fits = |a: &Option<String>, b: &Option<String>| a.is_none() || b.is_none() || a == b;
+            if let Some(e_rel) = self.relations.iter_mut().find(|r| r.from == rel.to && r.to == rel.from && r.kind == rel.kind && fits(&r.from_role, &rel.to_role) && fits(&r.to_role, &rel.from_role)) {
+                e_rel.change_relation_kind(UMLRelationKind::UMLAssociationBi);
+                // the other direction brings the multiplicity and role of the existing relation's source
+                if rel.to_multiplicity.is_some() {
+                    e_rel.from_multiplicity = rel.to_multiplicity;
                 }
-            }                    
+                e_rel.from_role = e_rel.from_role.take().or(rel.to_role);
+                e_rel.to_role = e_rel.to_role.take().or(rel.from_role);
+                return
+            }
         }
-        else {
-            dbg!("warning: this graph cannot add Relation now", &rel);
+
+        // a relation without role gives way to a not weaker one with role
+        if rel.to_role.is_some() || rel.from_role.is_some() {
+            if let Some(e_rel) = self.relations.iter_mut().find(|r| r.from == rel.from && r.to == rel.to && r.to_role.is_none() && r.from_role.is_none() && r.kind <= rel.kind) {
+                *e_rel = rel;
+                return
+            }
         }
-        
+        self.relations.push(rel);
     }
 
-    pub fn add_struct(&mut self, cls: UMLClass) -> () {
+    pub fn add_struct(&mut self, cls: UMLClass) {
         // add struct, if exists, extend its methods
         if self.get_struct_names().contains(&cls.name) {
             self.get_mut_struct(&cls.name).unwrap().merge_method_names_from(&mut cls.clone());
@@ -174,11 +299,42 @@ impl UMLGraph {
         }
     }
 
-    pub fn add_fn(&mut self, f: UMLFn) -> () {
+    pub fn add_trait_impl(&mut self, mut cls: UMLClass) {
+        // trait impl methods only go to types in the graph, foreign ones get no node for them
+        if let Some(st) = self.get_mut_struct(&cls.name) {
+            st.merge_method_names_from(&mut cls);
+        }
+    }
+
+    pub fn add_fn(&mut self, f: UMLFn) {
         self.fns.push(f);
     }
 
-    pub fn add_outer_entity(&mut self, outer_entity: UMLOuterEntity) -> () {
+    pub fn add_macro(&mut self, m: UMLMacro) {
+        self.macros.push(m);
+    }
+
+    pub fn add_static(&mut self, s: UMLStatic) {
+        self.statics.push(s);
+    }
+
+    pub fn add_extern_crate(&mut self, name: &str) {
+        self.extern_crates.push(String::from(name));
+    }
+
+    pub fn add_outer_entity(&mut self, outer_entity: UMLOuterEntity) {
         self.outer_entities.push(outer_entity);
     }
-}
\ No newline at end of file
+}
+// traits and types in scope everywhere, by their full paths
+const PRELUDE_PATHS: [(&str, &str); 27] = [
+    ("AsMut", "std.convert.AsMut"), ("AsRef", "std.convert.AsRef"), ("Box", "std.boxed.Box"),
+    ("Clone", "std.clone.Clone"), ("Copy", "std.marker.Copy"), ("Default", "std.default.Default"),
+    ("DoubleEndedIterator", "std.iter.DoubleEndedIterator"), ("Drop", "std.ops.Drop"), ("Eq", "std.cmp.Eq"),
+    ("ExactSizeIterator", "std.iter.ExactSizeIterator"), ("Extend", "std.iter.Extend"), ("Fn", "std.ops.Fn"),
+    ("FnMut", "std.ops.FnMut"), ("FnOnce", "std.ops.FnOnce"), ("From", "std.convert.From"),
+    ("FromIterator", "std.iter.FromIterator"), ("Into", "std.convert.Into"), ("IntoIterator", "std.iter.IntoIterator"),
+    ("Iterator", "std.iter.Iterator"), ("Ord", "std.cmp.Ord"), ("PartialEq", "std.cmp.PartialEq"),
+    ("PartialOrd", "std.cmp.PartialOrd"), ("Send", "std.marker.Send"), ("Sized", "std.marker.Sized"),
+    ("String", "std.string.String"), ("Sync", "std.marker.Sync"), ("ToString", "std.string.ToString"),
+];
@@ -0,0 +1,36 @@
+use super::UMLVisibility;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct UMLMacro {
+    pub name: String,
+    // matchers of the macro's rules, one per arm
+    pub rules: Vec<String>,
+    pub visibility: UMLVisibility,
+    pub doc: Option<String>
+}
+
+impl UMLMacro {
+    pub fn new(name: &str, rules: Vec<String>) -> UMLMacro {
+        UMLMacro { name: String::from(name), rules, visibility: UMLVisibility::default(), doc: None }
+    }
+
+    pub fn get_std_path(name: &str) -> Option<String> {
+        // well-known std macros are named by their full paths, so they never stand for local items
+        match STD_MACRO_NAMES.contains(&name) {
+            true => Some(["std.", name].concat()),
+            false => None
+        }
+    }
+
+    pub fn is_std_path(path: &str) -> bool {
+        path.strip_prefix("std.").is_some_and(|name| STD_MACRO_NAMES.contains(&name))
+    }
+}
+
+const STD_MACRO_NAMES: [&str; 36] = [
+    "assert", "assert_eq", "assert_ne", "cfg", "column", "compile_error", "concat", "dbg",
+    "debug_assert", "debug_assert_eq", "debug_assert_ne", "env", "eprint", "eprintln", "file", "format",
+    "format_args", "include", "include_bytes", "include_str", "line", "matches", "module_path", "option_env",
+    "panic", "print", "println", "stringify", "thread_local", "todo", "try", "unimplemented",
+    "unreachable", "vec", "write", "writeln",
+];
@@ -0,0 +1,21 @@
+#[derive(PartialEq, Debug, Clone)]
+pub enum UMLQualifier {
+    // in the order they are written in a fn header
+    Const,
+    Async,
+    Unsafe,
+    // `extern` with its ABI, like `C`, if there is any
+    Extern(Option<String>),
+}
+
+impl UMLQualifier {
+    pub fn get_stereotype_name(&self) -> String {
+        match self {
+            UMLQualifier::Const => String::from("const"),
+            UMLQualifier::Async => String::from("async"),
+            UMLQualifier::Unsafe => String::from("unsafe"),
+            UMLQualifier::Extern(Some(abi)) => format!("extern \"{}\"", abi),
+            UMLQualifier::Extern(None) => String::from("extern"),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+#[derive(PartialEq, Debug, Clone)]
+pub enum UMLReceiver {
+    // `self` or `mut self`
+    Value,
+    // `&self` or `&'a self`
+    Ref,
+    // `&mut self` or `&'a mut self`
+    RefMut,
+    // `self: Box<Self>` and other explicitly typed receivers, with the type
+    Typed(String),
+}
+
+impl UMLReceiver {
+    pub fn from_param_text(param: &str) -> Option<UMLReceiver> {
+        // the receiver is always the first param, if there is any
+        let param = param.trim();
+        if let Some((pat, ty)) = param.split_once(':') {
+            let pat = pat.trim();
+            return match pat.strip_prefix("mut ").unwrap_or(pat).trim() == "self" {
+                true => Some(UMLReceiver::Typed(String::from(ty.trim()))),
+                false => None
+            }
+        }
+
+        let (is_ref, rest) = match param.strip_prefix('&') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, param)
+        };
+        // skip the lifetime of the reference
+        let rest = match rest.starts_with('\'') {
+            true => rest.split_once(char::is_whitespace).map(|(_, r)| r.trim_start()).unwrap_or_default(),
+            false => rest
+        };
+        let (is_mut, rest) = match rest.strip_prefix("mut ") {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, rest)
+        };
+        if rest != "self" {
+            return None
+        }
+        match (is_ref, is_mut) {
+            (true, true) => Some(UMLReceiver::RefMut),
+            (true, false) => Some(UMLReceiver::Ref),
+            (false, _) => Some(UMLReceiver::Value),
+        }
+    }
+}
@@ -1,32 +1,59 @@
 
+#[allow(clippy::enum_variant_names)]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
 pub enum UMLRelationKind {
     // UML relation types
     UMLDependency=0,
-    UMLAssociationUni=1,
-    UMLAssociationBi=2,
-    UMLAggregation=3,
-    UMLComposition=4,
-    UMLRealization=5
+    // dependency on a trait through `dyn Trait` or `impl Trait`
+    UMLInterfaceUsage=1,
+    UMLAssociationUni=2,
+    UMLAssociationBi=3,
+    UMLAggregation=4,
+    UMLComposition=5,
+    UMLRealization=6,
+    UMLGeneralization=7
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
 pub struct UMLRelation {
     pub from: String,
     pub to: String,
-    pub kind: UMLRelationKind
+    pub kind: UMLRelationKind,
+    // multiplicities at both ends, as `1`, `0..1` or `0..*`
+    pub from_multiplicity: Option<String>,
+    pub to_multiplicity: Option<String>,
+    // role names at both ends, taken from the originating field or method
+    pub from_role: Option<String>,
+    pub to_role: Option<String>,
+    // associated type bindings of a used interface, like `Item = Row`
+    pub bindings: Vec<String>
 }
 
 impl UMLRelation {
     pub fn new(from: &str, to: &str, kind: UMLRelationKind) -> UMLRelation {
-        UMLRelation { from: String::from(from), to: String::from(to), kind: kind }
+        UMLRelation { from: String::from(from), to: String::from(to), kind, from_multiplicity: None, to_multiplicity: None, from_role: None, to_role: None, bindings: vec![] }
     }
 
-    pub fn change_relation_kind(&mut self, new_relation_kind: UMLRelationKind) -> () {
+    pub fn new_with_multiplicities(from: &str, to: &str, kind: UMLRelationKind, from_multiplicity: Option<&str>, to_multiplicity: Option<&str>) -> UMLRelation {
+        let mut rel = UMLRelation::new(from, to, kind);
+        rel.set_multiplicities(from_multiplicity, to_multiplicity);
+        rel
+    }
+
+    pub fn set_role(&mut self, role: &str) {
+        self.to_role = Some(String::from(role));
+    }
+
+    pub fn set_multiplicities(&mut self, from_multiplicity: Option<&str>, to_multiplicity: Option<&str>) {
+        self.from_multiplicity = from_multiplicity.map(String::from);
+        self.to_multiplicity = to_multiplicity.map(String::from);
+    }
+
+    pub fn change_relation_kind(&mut self, new_relation_kind: UMLRelationKind) {
         self.kind = new_relation_kind
     }
 
-    pub fn update_relation_names(&mut self, from: &str, to: &str) -> () {
+    pub fn update_relation_names(&mut self, from: &str, to: &str) {
         self.from = String::from(from);
         self.to = String::from(to);
     }
@@ -0,0 +1,16 @@
+use super::UMLVisibility;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct UMLStatic {
+    pub name: String,
+    // with `mut` and the type, like `static mut errno: c_int`
+    pub full_name: String,
+    pub visibility: UMLVisibility,
+    pub doc: Option<String>
+}
+
+impl UMLStatic {
+    pub fn new(name: &str, full_name: &str) -> UMLStatic {
+        UMLStatic { name: String::from(name), full_name: String::from(full_name), visibility: UMLVisibility::default(), doc: None }
+    }
+}
@@ -0,0 +1,15 @@
+#[derive(PartialEq, Debug, Clone, Default)]
+pub enum UMLVisibility {
+    // Rust visibilities mapped to UML ones: pub, pub(crate), pub(super) or pub(in path), and private
+    Public,
+    Crate,
+    Restricted,
+    #[default]
+    Private,
+}
+
+impl UMLVisibility {
+    pub fn is_public(&self) -> bool {
+        self == &UMLVisibility::Public
+    }
+}
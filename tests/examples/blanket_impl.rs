@@ -0,0 +1,23 @@
+use std::fmt::Display;
+
+trait ToPretty {
+    fn pretty(&self) -> String;
+}
+
+impl<T: Display> ToPretty for T {
+    fn pretty(&self) -> String {
+        format!("<{}>", self)
+    }
+}
+
+trait Named {}
+
+impl<T> Named for &T where T: Named + ?Sized {}
+
+struct Wrapper<T>(T);
+
+impl<T> From<T> for Wrapper<T> {
+    fn from(t: T) -> Self {
+        Wrapper(t)
+    }
+}
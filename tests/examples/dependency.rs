@@ -7,4 +7,7 @@ impl A {
 }
 
 struct B {
-}
\ No newline at end of file
+}
+fn make() {
+    let b = B::new();
+}
@@ -0,0 +1,12 @@
+trait Named {}
+
+#[derive(Debug, Clone)]
+struct A {
+    a: usize,
+}
+
+#[derive(Debug, Named)]
+#[derive(serde::Serialize)]
+enum B {
+    B1,
+}
@@ -0,0 +1,23 @@
+//! Shapes and how to draw them.
+
+/// Something that can be drawn.
+trait Draw {
+    /// Draws on the "screen".
+    fn draw(&self);
+}
+
+/// A point on the canvas.
+/// Both coordinates are in pixels.
+struct Point {
+    /// horizontal coordinate
+    x: i32,
+    y: i32,
+}
+
+/// Utilities for points.
+mod utils {
+    //! Nothing is drawn here.
+
+    /// Origin of the canvas.
+    fn origin() {}
+}
@@ -0,0 +1,8 @@
+enum Message {
+    Quit,
+    Write(Text),
+    Move { x: i32, y: i32 },
+}
+
+struct Text {
+}
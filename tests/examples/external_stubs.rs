@@ -0,0 +1,21 @@
+use std::fmt::Debug;
+
+struct A;
+
+impl Debug for A {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        Ok(())
+    }
+}
+
+struct Walker {
+    client: reqwest::Client,
+    name: String,
+}
+
+impl Iterator for Walker {
+    type Item = A;
+    fn next(&mut self) -> Option<A> {
+        None
+    }
+}
@@ -0,0 +1,18 @@
+extern crate libc;
+
+extern "C" {
+    fn strlen(s: *const u8) -> usize;
+    static mut errno: i32;
+}
+
+struct CString(*const u8);
+
+impl CString {
+    fn len(&self) -> usize {
+        unsafe { strlen(self.0) }
+    }
+}
+
+fn main() {
+    let n = unsafe { strlen(std::ptr::null()) };
+}
@@ -0,0 +1,8 @@
+use std::fmt::Debug;
+
+trait Source: Debug {
+    fn read(&mut self) -> usize;
+}
+
+trait Reader: Source + Send {
+}
@@ -0,0 +1,15 @@
+fn hello() {
+}
+
+mod tests {
+    use super::hello;
+
+    fn mock() {
+        hello();
+    }
+
+    mod nested {
+        struct A {
+        }
+    }
+}
@@ -0,0 +1,25 @@
+trait Storage {
+    fn get(&self, key: &str) -> Option<Row>;
+}
+
+struct Row;
+
+struct Table;
+
+impl Table {
+    fn rows(&self, storage: &dyn Storage) -> impl Iterator<Item = Row> {
+        std::iter::empty()
+    }
+}
+
+fn open() -> Box<dyn Storage + Send> {
+    unimplemented!()
+}
+
+trait Cursor {
+    type Item;
+}
+
+fn scan(storage: &impl Storage) -> Box<dyn Cursor<Item = Row>> {
+    unimplemented!()
+}
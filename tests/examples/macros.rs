@@ -0,0 +1,24 @@
+/// Squares an expression.
+#[macro_export]
+macro_rules! square {
+    ($x:expr) => { $x * $x };
+    ($x:expr, $y:expr) => { $x * $y };
+}
+
+macro_rules! noop {
+    () => {};
+}
+
+struct A;
+
+impl A {
+    fn area(&self) -> u32 {
+        noop!();
+        square!(2)
+    }
+}
+
+fn main() {
+    println!("{}", square!(3));
+    let v = vec![1];
+}
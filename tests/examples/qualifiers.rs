@@ -0,0 +1,11 @@
+struct Conn;
+
+impl Conn {
+    pub async fn send(&self) {}
+    pub const fn new() -> Self { Conn }
+    unsafe fn raw(&self) -> *const u8 { std::ptr::null() }
+}
+
+pub extern "C" fn callback(code: i32) {}
+
+unsafe fn poke() {}
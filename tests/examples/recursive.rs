@@ -0,0 +1,19 @@
+struct Node {
+    next: Option<Box<Node>>,
+    children: Vec<Node>,
+    parent: std::rc::Weak<Node>,
+}
+
+impl Node {
+    fn new() -> Node {
+        Node { next: None, children: vec![], parent: std::rc::Weak::new() }
+    }
+}
+
+fn depth(n: &Node) -> usize {
+    n.children.iter().map(depth).max().unwrap_or(0) + 1
+}
+
+fn fact(n: u64) -> u64 {
+    if n == 0 { 1 } else { n * fact(n - 1) }
+}
@@ -0,0 +1,15 @@
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+
+struct Leaf;
+struct Config;
+struct Cache;
+struct Parent;
+
+struct Node<'a> {
+    leaf: Box<Leaf>,
+    children: Vec<Option<Leaf>>,
+    config: Arc<Config>,
+    cache: &'a Cache,
+    parent: Weak<Parent>,
+}
@@ -0,0 +1,5 @@
+struct Inner;
+
+struct Meters(f64);
+
+struct Wrapper(pub Inner, *const Inner);
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use rudg::{code_to_dot_digraph};
+    use rudg::{code_to_dot_digraph, ExportOptions, DeriveStyle};
 
     #[test]
     fn parse_simple_r_code() {
@@ -30,7 +30,7 @@ r#"digraph ast {
         assert_eq!(
             code_to_dot_digraph(code), 
 r#"digraph ast {
-    "Mock"[label="{Mock|mock_fn()}"][shape="record"];
+    "Mock"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Mock</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>+mock_fn()</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
 }
 "#
         )
@@ -49,7 +49,7 @@ r#"digraph ast {
         assert_eq!(
             code_to_dot_digraph(code), 
 r#"digraph ast {
-    "Mock"[label="{Mock|mock_fn()}"][shape="record"];
+    "Mock"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Mock</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>+mock_fn()</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
     "f1"[label="f1"];
     "f2"[label="f2"];
     "Mock" -> "f1"[label=""][style="dashed"][arrowhead="vee"];
@@ -59,6 +59,26 @@ r#"digraph ast {
         )
     }
 
+    #[test]
+    fn parse_class_with_generic_members() {
+        let code: &str = r#"
+            pub struct Stack<T> {
+                items: Vec<T>,
+            }
+            impl<T> Stack<T> {
+                pub fn pop(&mut self) -> Option<T> { self.items.pop() }
+            }
+        "#;
+        // angle brackets and ampersands are escaped in the HTML-like label, in types and after `->` alike
+        assert_eq!(
+            code_to_dot_digraph(code),
+r#"digraph ast {
+    "Stack"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>T<BR ALIGN="RIGHT"/>Stack</TD></TR><HR/><TR><TD ALIGN="LEFT">-items: Vec&lt;T&gt;<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">+pop(&amp;mut self) -&gt; Option&lt;T&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#
+        )
+    }
+
     #[test]
     fn test_fn_dependency() {
         let code: &str = r#"
@@ -128,11 +148,11 @@ r#"digraph ast {
         assert_eq!(
             rudg::rs2dot("tests/examples/aggregation.rs"),
 r#"digraph ast {
-    "Amut"[label="{Amut|b: *mut B}"][shape="record"];
-    "Aconst"[label="{Aconst|b: *const B}"][shape="record"];
-    "B"[label="B"][shape="record"];
-    "Amut" -> "B"[label=""][arrowtail="odiamond"];
-    "Aconst" -> "B"[label=""][arrowtail="odiamond"];
+    "Amut"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Amut</TD></TR><HR/><TR><TD ALIGN="LEFT">-b: *mut B<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Aconst"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Aconst</TD></TR><HR/><TR><TD ALIGN="LEFT">-b: *const B<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>B</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Amut" -> "B"[label=""][headlabel="b\n1"][arrowtail="odiamond"];
+    "Aconst" -> "B"[label=""][headlabel="b\n1"][arrowtail="odiamond"];
 }
 "#
         )
@@ -143,11 +163,11 @@ r#"digraph ast {
         assert_eq!(
             rudg::rs2dot("tests/examples/association.rs"),
 r#"digraph ast {
-    "A"[label="{A|b() -> B}"][shape="record"];
-    "Ab"[label="{Ab|b() -> B}"][shape="record"];
-    "B"[label="{B|a() -> Ab}"][shape="record"];
-    "B" -> "A"[label=""][arrowhead="vee"];
-    "B" -> "Ab"[label=""][arrowhead="none"];
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-b() -&gt; B</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Ab"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Ab</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-b() -&gt; B</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>B</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-a() -&gt; Ab</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B" -> "A"[label=""][taillabel="b"][arrowhead="vee"];
+    "B" -> "Ab"[label=""][headlabel="a"][taillabel="b"][arrowhead="none"];
 }
 "#
         )
@@ -158,9 +178,9 @@ r#"digraph ast {
         assert_eq!(
             rudg::rs2dot("tests/examples/composition.rs"),
 r#"digraph ast {
-    "A"[label="{A|b: B}"][shape="record"];
-    "B"[label="B"][shape="record"];
-    "A" -> "B"[label=""][arrowhead="diamond"];
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR><HR/><TR><TD ALIGN="LEFT">-b: B<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>B</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "A" -> "B"[label=""][headlabel="b\n1"][taillabel="1"][arrowhead="diamond"];
 }
 "#
     );
@@ -171,9 +191,11 @@ r#"digraph ast {
         assert_eq!(
             rudg::rs2dot("tests/examples/dependency.rs"),
 r#"digraph ast {
-    "A"[label="{A|b(b: &B)}"][shape="record"];
-    "B"[label="B"][shape="record"];
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-b(b: &amp;B)</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>B</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "make"[label="make"];
     "A" -> "B"[label=""][style="dashed"][arrowhead="vee"];
+    "make" -> "B"[label=""][style="dashed"][arrowhead="vee"];
 }
 "#
     );
@@ -184,9 +206,377 @@ r#"digraph ast {
         assert_eq!(
             rudg::rs2dot("tests/examples/realization.rs"),
 r#"digraph ast {
-    "A"[label="{A|a: T|a(a: T) -> Self}"][shape="record"];
-    "B"[label="{Interface\lB|a(&self) -> Option<T>}"][shape="record"];
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>T: Debug<BR ALIGN="RIGHT"/>&lt;&lt;derive: Debug&gt;&gt;<BR ALIGN="LEFT"/>A</TD></TR><HR/><TR><TD ALIGN="LEFT">-a: T<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-a(a: T) -&gt; Self</U><BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">B&lt;T&gt;: a(&amp;self) -&gt; Option&lt;T&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>T: Debug<BR ALIGN="RIGHT"/>Interface<BR ALIGN="LEFT"/>B</TD></TR><HR/><TR><TD ALIGN="LEFT">+a(&amp;self) -&gt; Option&lt;T&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
     "A" -> "B"[label=""][style="dashed"][arrowhead="onormal"];
+    "A" -> "std.fmt.Debug"[label=""][style="dashed"][arrowhead="vee"];
+    "B" -> "std.fmt.Debug"[label=""][arrowhead="onormal"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_generalization() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/generalization.rs"),
+r#"digraph ast {
+    "Source"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Source</TD></TR><HR/><TR><TD ALIGN="LEFT">+read(&amp;mut self) -&gt; usize<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Reader"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Reader</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Reader" -> "Source"[label=""][arrowhead="onormal"];
+    "Source" -> "std.fmt.Debug"[label=""][arrowhead="onormal"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_enumeration() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/enumeration.rs"),
+r#"digraph ast {
+    "Message"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>&lt;&lt;enumeration&gt;&gt;<BR ALIGN="LEFT"/>Message</TD></TR><HR/><TR><TD ALIGN="LEFT">Quit<BR ALIGN="LEFT"/>Write(Text)<BR ALIGN="LEFT"/>Move { x: i32, y: i32 }<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Text"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Text</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Message" -> "Text"[label=""][headlabel="Write\n1"][taillabel="1"][arrowhead="diamond"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_inline_modules() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/inline_modules.rs"),
+r#"digraph ast {
+    subgraph cluster_tests {
+        label="tests";
+        "tests.mock"[label="mock"];
+        "tests.mock" -> "hello"[label=""][style="dashed"][arrowhead="vee"];
+        subgraph cluster_tests_nested {
+            label="nested";
+            "tests.nested.A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR></TABLE>>][margin="0"][shape="box"];
+        }
+    }
+    "hello"[label="hello"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_child_module_paths() {
+        let code: &str = r#"
+            mod m {
+                pub struct S;
+                pub fn f() {}
+            }
+            struct T { s: m::S }
+            fn g() { m::f(); }
+        "#;
+        assert_eq!(
+            code_to_dot_digraph(code),
+r#"digraph ast {
+    subgraph cluster_m {
+        label="m";
+        "m.S"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>S</TD></TR></TABLE>>][margin="0"][shape="box"];
+        "m.f"[label="f"];
+    }
+    "T"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>T</TD></TR><HR/><TR><TD ALIGN="LEFT">-s: m::S<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "g"[label="g"];
+    "T" -> "m.S"[label=""][headlabel="s\n1"][taillabel="1"][arrowhead="diamond"];
+    "g" -> "m.f"[label=""][style="dashed"][arrowhead="vee"];
+}
+"#
+        )
+    }
+
+    #[test]
+    fn test_derive() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/derive.rs"),
+r#"digraph ast {
+    "Named"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Named</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>&lt;&lt;derive: Debug, Clone&gt;&gt;<BR ALIGN="LEFT"/>A</TD></TR><HR/><TR><TD ALIGN="LEFT">-a: usize<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>&lt;&lt;derive: Debug, Named, serde::Serialize&gt;&gt;<BR ALIGN="LEFT"/>&lt;&lt;enumeration&gt;&gt;<BR ALIGN="LEFT"/>B</TD></TR><HR/><TR><TD ALIGN="LEFT">B1<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_derive_realizations() {
+        let options = ExportOptions { derive_style: DeriveStyle::Realization, ..Default::default() };
+        assert_eq!(
+            rudg::rs2dot_with_options("tests/examples/derive.rs", &options),
+r#"digraph ast {
+    "Named"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Named</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR><HR/><TR><TD ALIGN="LEFT">-a: usize<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "B"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>&lt;&lt;enumeration&gt;&gt;<BR ALIGN="LEFT"/>B</TD></TR><HR/><TR><TD ALIGN="LEFT">B1<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Debug"[label="Interface\lDebug"][style="dashed"][shape="record"];
+    "Clone"[label="Interface\lClone"][style="dashed"][shape="record"];
+    "serde.Serialize"[label="Interface\lserde::Serialize"][style="dashed"][shape="record"];
+    "A" -> "Debug"[label=""][style="dashed"][arrowhead="onormal"];
+    "A" -> "Clone"[label=""][style="dashed"][arrowhead="onormal"];
+    "B" -> "Debug"[label=""][style="dashed"][arrowhead="onormal"];
+    "B" -> "Named"[label=""][style="dashed"][arrowhead="onormal"];
+    "B" -> "serde.Serialize"[label=""][style="dashed"][arrowhead="onormal"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/doc_comments.rs"),
+r#"digraph ast {
+    tooltip="Shapes and how to draw them.";
+    subgraph cluster_utils {
+        label="utils";
+        tooltip="Utilities for points.\nNothing is drawn here.";
+        "utils.origin"[label="origin"][tooltip="Origin of the canvas."];
+    }
+    "Draw"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Draw</TD></TR><HR/><TR><TD ALIGN="LEFT">+draw(&amp;self)<BR ALIGN="LEFT"/></TD></TR></TABLE>>][tooltip="Something that can be drawn.\ndraw(): Draws on the \"screen\"."][margin="0"][shape="box"];
+    "Point"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Point</TD></TR><HR/><TR><TD ALIGN="LEFT">-x: i32<BR ALIGN="LEFT"/>-y: i32<BR ALIGN="LEFT"/></TD></TR></TABLE>>][tooltip="A point on the canvas.\nBoth coordinates are in pixels.\nx: horizontal coordinate"][margin="0"][shape="box"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_doc_notes() {
+        let options = ExportOptions { doc_notes: true, ..Default::default() };
+        assert_eq!(
+            rudg::rs2dot_with_options("tests/examples/doc_comments.rs", &options),
+r#"digraph ast {
+    tooltip="Shapes and how to draw them.";
+    subgraph cluster_utils {
+        label="utils";
+        tooltip="Utilities for points.\nNothing is drawn here.";
+        "utils.origin"[label="origin"][tooltip="Origin of the canvas."];
+        "utils.origin__doc"[label="Origin of the canvas.\l"][shape="note"];
+        "utils.origin__doc" -> "utils.origin"[label=""][style="dashed"][arrowhead="none"];
+    }
+    "Draw"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Draw</TD></TR><HR/><TR><TD ALIGN="LEFT">+draw(&amp;self)<BR ALIGN="LEFT"/></TD></TR></TABLE>>][tooltip="Something that can be drawn.\ndraw(): Draws on the \"screen\"."][margin="0"][shape="box"];
+    "Draw__doc"[label="Something that can be drawn.\ldraw(): Draws on the \"screen\".\l"][shape="note"];
+    "Point"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Point</TD></TR><HR/><TR><TD ALIGN="LEFT">-x: i32<BR ALIGN="LEFT"/>-y: i32<BR ALIGN="LEFT"/></TD></TR></TABLE>>][tooltip="A point on the canvas.\nBoth coordinates are in pixels.\nx: horizontal coordinate"][margin="0"][shape="box"];
+    "Point__doc"[label="A point on the canvas.\lBoth coordinates are in pixels.\lx: horizontal coordinate\l"][shape="note"];
+    "Draw__doc" -> "Draw"[label=""][style="dashed"][arrowhead="none"];
+    "Point__doc" -> "Point"[label=""][style="dashed"][arrowhead="none"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_qualifiers() {
+        let options = ExportOptions { highlight_unsafe: true, ..Default::default() };
+        assert_eq!(
+            rudg::rs2dot_with_options("tests/examples/qualifiers.rs", &options),
+r#"digraph ast {
+    "Conn"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Conn</TD></TR><HR/><TR><TD ALIGN="LEFT">«async» +send(&amp;self)<BR ALIGN="LEFT"/>«const» <U>+new() -&gt; Self</U><BR ALIGN="LEFT"/>«unsafe» -raw(&amp;self) -&gt; *const u8<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][color="red"][shape="box"];
+    "callback"[label="«extern \"C\"» callback"];
+    "poke"[label="«unsafe» poke"][color="red"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_external_stubs() {
+        let options = ExportOptions { external_stubs: true, ..Default::default() };
+        assert_eq!(
+            rudg::rs2dot_with_options("tests/examples/external_stubs.rs", &options),
+r#"digraph ast {
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR><HR/><TR><TD ALIGN="LEFT">Debug: fmt(&amp;self, f: &amp;mut Formatter) -&gt; Result<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Walker"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Walker</TD></TR><HR/><TR><TD ALIGN="LEFT">-client: reqwest::Client<BR ALIGN="LEFT"/>-name: String<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">Iterator: next(&amp;mut self) -&gt; Option&lt;A&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "reqwest.Client"[label="reqwest::Client"][style="dashed"][color="gray"][shape="record"];
+    "std.string.String"[label="std::string::String"][style="dashed"][color="gray"][shape="record"];
+    "std.iter.Iterator"[label="Interface\lstd::iter::Iterator"][style="dashed"][color="gray"][shape="record"];
+    "std.fmt.Debug"[label="Interface\lstd::fmt::Debug"][style="dashed"][color="gray"][shape="record"];
+    "A" -> "Walker"[label=""][taillabel="next"][arrowhead="vee"];
+    "A" -> "std.fmt.Debug"[label=""][style="dashed"][arrowhead="onormal"];
+    "Walker" -> "reqwest.Client"[label=""][headlabel="client\n1"][taillabel="1"][arrowhead="diamond"];
+    "Walker" -> "std.string.String"[label=""][headlabel="name\n1"][taillabel="1"][arrowhead="diamond"];
+    "Walker" -> "std.iter.Iterator"[label=""][style="dashed"][arrowhead="onormal"];
+}
+"#
+        );
+
+        // only the crates allowed and not denied get stubs
+        let options = ExportOptions { external_stubs: true, stub_crates_allowed: vec![String::from("std")], stub_crates_denied: vec![String::from("std"), String::from("core")], ..Default::default() };
+        assert_eq!(
+            rudg::rs2dot_with_options("tests/examples/external_stubs.rs", &options),
+r#"digraph ast {
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR><HR/><TR><TD ALIGN="LEFT">Debug: fmt(&amp;self, f: &amp;mut Formatter) -&gt; Result<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Walker"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Walker</TD></TR><HR/><TR><TD ALIGN="LEFT">-client: reqwest::Client<BR ALIGN="LEFT"/>-name: String<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">Iterator: next(&amp;mut self) -&gt; Option&lt;A&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "A" -> "Walker"[label=""][taillabel="next"][arrowhead="vee"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_ffi() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/ffi.rs"),
+r#"digraph ast {
+    subgraph cluster_extern_C {
+        label="<<foreign>>\nextern_C";
+        "extern_C.strlen"[label="strlen"];
+        "extern_C.errno"[label="static mut errno: i32"][shape="box"];
+    }
+    "CString"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>CString</TD></TR><HR/><TR><TD ALIGN="LEFT">-0: *const u8<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">-len(&amp;self) -&gt; usize<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "main"[label="main"];
+    "extern_crate_libc"[label="<<extern crate>>\nlibc"][shape="folder"];
+    "CString" -> "extern_C.strlen"[label=""][style="dashed"][arrowhead="vee"];
+    "main" -> "extern_C.strlen"[label=""][style="dashed"][arrowhead="vee"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_blanket_impl() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/blanket_impl.rs"),
+r#"digraph ast {
+    "ToPretty"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>ToPretty</TD></TR><HR/><TR><TD ALIGN="LEFT">+pretty(&amp;self) -&gt; String<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "__blanket_ToPretty_for_T"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>any T: Display</TD></TR><HR/><TR><TD ALIGN="LEFT">ToPretty: pretty(&amp;self) -&gt; String<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][style="dashed"][shape="box"];
+    "Named"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Named</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "__blanket_Named_for_ref_T"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>any &amp;T where T: Named + ?Sized</TD></TR></TABLE>>][margin="0"][style="dashed"][shape="box"];
+    "Wrapper"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>T<BR ALIGN="RIGHT"/>Wrapper</TD></TR><HR/><TR><TD ALIGN="LEFT">-0: T<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT">From&lt;T&gt;: <U>from(t: T) -&gt; Self</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "__blanket_ToPretty_for_T" -> "ToPretty"[label=""][style="dashed"][arrowhead="onormal"];
+    "__blanket_Named_for_ref_T" -> "Named"[label=""][style="dashed"][arrowhead="onormal"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_interface_usage() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/interface_usage.rs"),
+r#"digraph ast {
+    "Storage"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Storage</TD></TR><HR/><TR><TD ALIGN="LEFT">+get(&amp;self, key: &amp;str) -&gt; Option&lt;Row&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Row"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Row</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Table"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Table</TD></TR><HR/><TR><TD ALIGN="LEFT">-rows(&amp;self, storage: &amp;dyn Storage) -&gt; impl Iterator&lt;Item = Row&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Cursor"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Cursor</TD></TR><HR/><TR><TD ALIGN="LEFT">+type Item<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "open"[label="open"];
+    "scan"[label="scan"];
+    "Table" -> "Storage"[label="«use»"][style="dashed"][arrowhead="vee"];
+    "open" -> "Storage"[label="«use»"][style="dashed"][arrowhead="vee"];
+    "scan" -> "Storage"[label="«use»"][style="dashed"][arrowhead="vee"];
+    "scan" -> "Cursor"[label="«use» Item = Row"][style="dashed"][arrowhead="vee"];
+}
+"#
+    );
+        // the bindings of traits of other crates are kept on the edges to their stubs
+        let options = rudg::ExportOptions { external_stubs: true, ..Default::default() };
+        assert_eq!(
+            rudg::rs2dot_with_options("tests/examples/interface_usage.rs", &options),
+r#"digraph ast {
+    "Storage"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Storage</TD></TR><HR/><TR><TD ALIGN="LEFT">+get(&amp;self, key: &amp;str) -&gt; Option&lt;Row&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Row"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Row</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Table"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Table</TD></TR><HR/><TR><TD ALIGN="LEFT">-rows(&amp;self, storage: &amp;dyn Storage) -&gt; impl Iterator&lt;Item = Row&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Cursor"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Interface<BR ALIGN="LEFT"/>Cursor</TD></TR><HR/><TR><TD ALIGN="LEFT">+type Item<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "open"[label="open"];
+    "scan"[label="scan"];
+    "std.iter.Iterator"[label="Interface\lstd::iter::Iterator"][style="dashed"][color="gray"][shape="record"];
+    "std.marker.Send"[label="Interface\lstd::marker::Send"][style="dashed"][color="gray"][shape="record"];
+    "Table" -> "Storage"[label="«use»"][style="dashed"][arrowhead="vee"];
+    "open" -> "Storage"[label="«use»"][style="dashed"][arrowhead="vee"];
+    "scan" -> "Storage"[label="«use»"][style="dashed"][arrowhead="vee"];
+    "scan" -> "Cursor"[label="«use» Item = Row"][style="dashed"][arrowhead="vee"];
+    "Table" -> "std.iter.Iterator"[label="«use» Item = Row"][style="dashed"][arrowhead="vee"];
+    "open" -> "std.marker.Send"[label="«use»"][style="dashed"][arrowhead="vee"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_recursive() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/recursive.rs"),
+r#"digraph ast {
+    "Node"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Node</TD></TR><HR/><TR><TD ALIGN="LEFT">-next: Option&lt;Box&lt;Node&gt;&gt;<BR ALIGN="LEFT"/>-children: Vec&lt;Node&gt;<BR ALIGN="LEFT"/>-parent: std::rc::Weak&lt;Node&gt;<BR ALIGN="LEFT"/></TD></TR><HR/><TR><TD ALIGN="LEFT"><U>-new() -&gt; Node</U><BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "depth"[label="depth"];
+    "fact"[label="fact"];
+    "Node" -> "Node"[label=""][headlabel="next\n0..1"][taillabel="1"][arrowhead="diamond"];
+    "Node" -> "Node"[label=""][headlabel="children\n0..*"][taillabel="1"][arrowhead="diamond"];
+    "Node" -> "Node"[label=""][headlabel="parent\n0..1"][arrowhead="vee"];
+    "fact" -> "fact"[label=""][style="dashed"][arrowhead="vee"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_tuple_struct() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/tuple_struct.rs"),
+r#"digraph ast {
+    "Inner"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Inner</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Meters"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Meters</TD></TR><HR/><TR><TD ALIGN="LEFT">-0: f64<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Wrapper"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Wrapper</TD></TR><HR/><TR><TD ALIGN="LEFT">+0: Inner<BR ALIGN="LEFT"/>-1: *const Inner<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Wrapper" -> "Inner"[label=""][headlabel="0\n1"][taillabel="1"][arrowhead="diamond"];
+    "Wrapper" -> "Inner"[label=""][headlabel="1\n1"][arrowtail="odiamond"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_macros() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/macros.rs"),
+r#"digraph ast {
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR><HR/><TR><TD ALIGN="LEFT">-area(&amp;self) -&gt; u32<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "main"[label="main"];
+    "square"[label="{\<\<macro\>\>\lsquare!|($x:expr)\l($x:expr, $y:expr)}"][tooltip="Squares an expression."][shape="record"];
+    "noop"[label="{\<\<macro\>\>\lnoop!|()}"][shape="record"];
+    "A" -> "noop"[label=""][style="dashed"][arrowhead="vee"];
+    "A" -> "square"[label=""][style="dashed"][arrowhead="vee"];
+    "main" -> "square"[label=""][style="dashed"][arrowhead="vee"];
+}
+"#
+    );
+        let options = rudg::ExportOptions { std_macros: true, ..Default::default() };
+        assert_eq!(
+            rudg::rs2dot_with_options("tests/examples/macros.rs", &options),
+r#"digraph ast {
+    "A"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>A</TD></TR><HR/><TR><TD ALIGN="LEFT">-area(&amp;self) -&gt; u32<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "main"[label="main"];
+    "square"[label="{\<\<macro\>\>\lsquare!|($x:expr)\l($x:expr, $y:expr)}"][tooltip="Squares an expression."][shape="record"];
+    "noop"[label="{\<\<macro\>\>\lnoop!|()}"][shape="record"];
+    "std.println"[label="\<\<macro\>\>\lstd::println!"][style="dashed"][color="gray"][shape="record"];
+    "std.vec"[label="\<\<macro\>\>\lstd::vec!"][style="dashed"][color="gray"][shape="record"];
+    "A" -> "noop"[label=""][style="dashed"][arrowhead="vee"];
+    "A" -> "square"[label=""][style="dashed"][arrowhead="vee"];
+    "main" -> "square"[label=""][style="dashed"][arrowhead="vee"];
+    "main" -> "std.println"[label=""][style="dashed"][arrowhead="vee"];
+    "main" -> "std.vec"[label=""][style="dashed"][arrowhead="vee"];
+}
+"#
+    );
+    }
+
+    #[test]
+    fn test_smart_pointers() {
+        assert_eq!(
+            rudg::rs2dot("tests/examples/smart_pointers.rs"),
+r#"digraph ast {
+    "Leaf"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Leaf</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Config"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Config</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Cache"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Cache</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Parent"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>Parent</TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Node"[label=<<TABLE BORDER="0" CELLSPACING="0"><TR><TD>'a<BR ALIGN="RIGHT"/>Node</TD></TR><HR/><TR><TD ALIGN="LEFT">-leaf: Box&lt;Leaf&gt;<BR ALIGN="LEFT"/>-children: Vec&lt;Option&lt;Leaf&gt;&gt;<BR ALIGN="LEFT"/>-config: Arc&lt;Config&gt;<BR ALIGN="LEFT"/>-cache: &amp;'a Cache<BR ALIGN="LEFT"/>-parent: Weak&lt;Parent&gt;<BR ALIGN="LEFT"/></TD></TR></TABLE>>][margin="0"][shape="box"];
+    "Node" -> "Leaf"[label=""][headlabel="leaf\n1"][taillabel="1"][arrowhead="diamond"];
+    "Node" -> "Leaf"[label=""][headlabel="children\n0..*"][taillabel="1"][arrowhead="diamond"];
+    "Node" -> "Config"[label=""][headlabel="config\n1"][arrowtail="odiamond"];
+    "Node" -> "Cache"[label=""][headlabel="cache\n1"][arrowtail="odiamond"];
+    "Node" -> "Parent"[label=""][headlabel="parent\n0..1"][arrowhead="vee"];
 }
 "#
     );